@@ -0,0 +1,34 @@
+use crate::AdminResponse;
+
+/// Returns true if the admin in `admin_response` is `me`, comparing the raw address strings.
+/// A denom without an admin is never administered by `me`.
+pub fn is_admin_str(admin_response: &AdminResponse, me: &str) -> bool {
+    !admin_response.admin.is_empty() && admin_response.admin == me
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admin(admin: &str) -> AdminResponse {
+        AdminResponse {
+            admin: admin.to_string(),
+        }
+    }
+
+    #[test]
+    fn matching_admin_is_me() {
+        assert!(is_admin_str(&admin("osmo1me"), "osmo1me"));
+    }
+
+    #[test]
+    fn other_admin_is_not_me() {
+        assert!(!is_admin_str(&admin("osmo1other"), "osmo1me"));
+    }
+
+    #[test]
+    fn empty_admin_is_never_me() {
+        assert!(!is_admin_str(&admin(""), "osmo1me"));
+        assert!(!is_admin_str(&admin(""), ""));
+    }
+}
@@ -0,0 +1,46 @@
+use cosmwasm_std::{Uint128, Uint256};
+
+/// An amount accepted by the CreateTokenFactoryMsg helpers, so call sites can pass a bank
+/// `Uint128`, a token factory `Uint256` or a plain `u128` without converting it first.
+///
+/// Since the helpers are generic over the amount, `.into()` at the call site no longer
+/// infers a type; pass the value as is instead.
+pub trait IntoAmount {
+    fn into_amount(self) -> Uint256;
+}
+
+impl IntoAmount for Uint128 {
+    fn into_amount(self) -> Uint256 {
+        self.into()
+    }
+}
+
+impl IntoAmount for Uint256 {
+    fn into_amount(self) -> Uint256 {
+        self
+    }
+}
+
+impl IntoAmount for u128 {
+    fn into_amount(self) -> Uint256 {
+        Uint256::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, CreateTokenFactoryMsg, TokenFactoryMsg};
+
+    fn mint(amount: impl IntoAmount) -> TokenFactoryMsg {
+        TokenFactoryMsg::token_factory_mint_tokens(fixtures::denom(), amount, fixtures::user())
+            .unwrap()
+    }
+
+    #[test]
+    fn mint_helper_accepts_every_amount_type() {
+        assert_eq!(mint(Uint128::new(1_000_000)), fixtures::mint_tokens());
+        assert_eq!(mint(Uint256::from(1_000_000u128)), fixtures::mint_tokens());
+        assert_eq!(mint(1_000_000u128), fixtures::mint_tokens());
+    }
+}
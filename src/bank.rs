@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Coin, CustomQuery, QuerierWrapper, StdResult, Uint128, Uint256};
+
+use crate::{DenomCreationFee, TokenFactoryMsg};
+
+// Bank-backed helpers behind the TokenFactoryBankQuerier default methods.
+
+pub(crate) fn burn_if_funded<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    denom: String,
+    amount: Uint256,
+    me: &Addr,
+) -> StdResult<Option<TokenFactoryMsg>> {
+    let balance = querier.query_balance(me, &denom)?;
+    if Uint256::from(balance.amount) < amount {
+        return Ok(None);
+    }
+    Ok(Some(TokenFactoryMsg::BurnTokens {
+        denom,
+        amount,
+        burn_from_address: me.clone(),
+    }))
+}
+
+pub(crate) fn creation_fee_shortfall<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    fees: Vec<DenomCreationFee>,
+    me: &Addr,
+) -> StdResult<Vec<Coin>> {
+    let mut required: BTreeMap<String, Uint256> = BTreeMap::new();
+    for fee in fees {
+        let total = required.entry(fee.denom).or_default();
+        *total = total.checked_add(fee.amount)?;
+    }
+
+    let mut shortfall = vec![];
+    for (denom, amount) in required {
+        let balance = Uint256::from(querier.query_balance(me, &denom)?.amount);
+        if balance < amount {
+            shortfall.push(Coin {
+                amount: Uint128::try_from(amount - balance)?,
+                denom,
+            });
+        }
+    }
+    Ok(shortfall)
+}
+
+#[cfg(feature = "cosmwasm_1_1")]
+pub(crate) fn mint_batch_within_cap<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    denom: String,
+    msgs: &[TokenFactoryMsg],
+    cap: Uint256,
+) -> StdResult<bool> {
+    let mut total = Uint256::from(querier.query_supply(&denom)?.amount);
+    for msg in msgs {
+        if let TokenFactoryMsg::MintTokens {
+            denom: minted,
+            amount,
+            ..
+        } = msg
+        {
+            if *minted == denom {
+                total = total.checked_add(*amount)?;
+            }
+        }
+    }
+    Ok(total <= cap)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coin;
+
+    use super::*;
+    use crate::testing::{mock_querier, respond, unsupported};
+    use crate::{fixtures, TokenFactoryBankQuerier, TokenFactoryQuery, TokenParamsResponse};
+
+    #[test]
+    fn burn_if_funded_burns_with_sufficient_balance() {
+        let me = fixtures::creator();
+        let querier = mock_querier(&[(me.as_str(), &[coin(100, fixtures::denom())])], |_| {
+            unsupported()
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let msg = querier
+            .burn_if_funded(fixtures::denom(), Uint256::from(100u128), &me)
+            .unwrap();
+        assert_eq!(
+            msg,
+            Some(TokenFactoryMsg::BurnTokens {
+                denom: fixtures::denom(),
+                amount: Uint256::from(100u128),
+                burn_from_address: me,
+            })
+        );
+    }
+
+    #[test]
+    fn burn_if_funded_skips_with_insufficient_balance() {
+        let me = fixtures::creator();
+        let querier = mock_querier(&[(me.as_str(), &[coin(99, fixtures::denom())])], |_| {
+            unsupported()
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let msg = querier
+            .burn_if_funded(fixtures::denom(), Uint256::from(100u128), &me)
+            .unwrap();
+        assert_eq!(msg, None);
+    }
+
+    fn shortfall_with_balance(balance: &[Coin]) -> Vec<Coin> {
+        let me = fixtures::creator();
+        let querier = mock_querier(&[(me.as_str(), balance)], |query| match query {
+            TokenFactoryQuery::Params {} => respond(&TokenParamsResponse {
+                params: fixtures::params(),
+            }),
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        querier.creation_fee_shortfall(&me).unwrap()
+    }
+
+    #[test]
+    fn creation_fee_shortfall_is_empty_when_fully_funded() {
+        assert_eq!(shortfall_with_balance(&[coin(10_000_000, "uosmo")]), vec![]);
+    }
+
+    #[test]
+    fn creation_fee_shortfall_reports_the_missing_part() {
+        assert_eq!(
+            shortfall_with_balance(&[coin(4_000_000, "uosmo")]),
+            vec![coin(6_000_000, "uosmo")]
+        );
+    }
+
+    #[test]
+    fn creation_fee_shortfall_reports_the_whole_fee_when_unfunded() {
+        assert_eq!(
+            shortfall_with_balance(&[coin(1_000, "uatom")]),
+            vec![coin(10_000_000, "uosmo")]
+        );
+    }
+
+    #[cfg(feature = "cosmwasm_1_1")]
+    #[test]
+    fn mint_batch_within_cap_adds_the_mints_to_the_supply() {
+        let supply = [coin(600_000, fixtures::denom())];
+        let querier = mock_querier(&[(fixtures::user().as_str(), &supply)], |_| unsupported());
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let msgs = [fixtures::mint_tokens(), fixtures::burn_tokens()];
+        assert!(querier
+            .mint_batch_within_cap(fixtures::denom(), &msgs, Uint256::from(1_600_000u128))
+            .unwrap());
+        assert!(!querier
+            .mint_batch_within_cap(fixtures::denom(), &msgs, Uint256::from(1_599_999u128))
+            .unwrap());
+    }
+}
@@ -0,0 +1,586 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Event, StdResult, SubMsg, Uint128, Uint256};
+
+use crate::{TokenFactoryError, TokenFactoryMsg, TokenParams};
+
+/// Builds one event per operation type and denom in the batch, carrying the number of
+/// messages and their total amount, instead of one set of attributes per message.
+///
+/// Events are `tf_mint_batch`, `tf_burn_batch` and `tf_force_transfer_batch`, each with
+/// `denom`, `count` and `total_amount` attributes, ordered by type and then denom.
+/// Operations that carry no amount are not included. Returns a `StdResult` rather than the
+/// events directly, since a total can overflow `Uint256`.
+pub fn batch_events(msgs: &[TokenFactoryMsg]) -> StdResult<Vec<Event>> {
+    let mut totals: BTreeMap<&'static str, BTreeMap<&str, (u64, Uint256)>> = BTreeMap::new();
+    for msg in msgs {
+        let (ty, denom, amount) = match msg {
+            TokenFactoryMsg::MintTokens { denom, amount, .. } => ("tf_mint_batch", denom, amount),
+            TokenFactoryMsg::BurnTokens { denom, amount, .. } => ("tf_burn_batch", denom, amount),
+            TokenFactoryMsg::ForceTransfer { denom, amount, .. } => {
+                ("tf_force_transfer_batch", denom, amount)
+            }
+            _ => continue,
+        };
+        let entry = totals
+            .entry(ty)
+            .or_default()
+            .entry(denom.as_str())
+            .or_default();
+        entry.0 += 1;
+        entry.1 = entry.1.checked_add(*amount)?;
+    }
+
+    Ok(totals
+        .into_iter()
+        .flat_map(|(ty, denoms)| {
+            denoms.into_iter().map(move |(denom, (count, total))| {
+                Event::new(ty)
+                    .add_attribute("denom", denom)
+                    .add_attribute("count", count.to_string())
+                    .add_attribute("total_amount", total.to_string())
+            })
+        })
+        .collect())
+}
+
+/// The kind of operation a [`TokenFactoryMsg`] performs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum TokenFactoryOp {
+    CreateDenom,
+    ChangeAdmin,
+    Mint,
+    Burn,
+    SetMetadata,
+    ForceTransfer,
+    SetBeforeSendHook,
+    #[cfg(feature = "multi-admin")]
+    ChangeAdmins,
+}
+
+impl TokenFactoryMsg {
+    /// Returns the kind of operation the message performs.
+    pub fn op(&self) -> TokenFactoryOp {
+        match self {
+            TokenFactoryMsg::CreateDenom { .. } => TokenFactoryOp::CreateDenom,
+            TokenFactoryMsg::ChangeAdmin { .. } => TokenFactoryOp::ChangeAdmin,
+            TokenFactoryMsg::MintTokens { .. } => TokenFactoryOp::Mint,
+            TokenFactoryMsg::BurnTokens { .. } => TokenFactoryOp::Burn,
+            TokenFactoryMsg::SetMetadata { .. } => TokenFactoryOp::SetMetadata,
+            TokenFactoryMsg::ForceTransfer { .. } => TokenFactoryOp::ForceTransfer,
+            TokenFactoryMsg::SetBeforeSendHook { .. } => TokenFactoryOp::SetBeforeSendHook,
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { .. } => TokenFactoryOp::ChangeAdmins,
+        }
+    }
+}
+
+/// Number of messages and total amount of one operation on one denom.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpTotals {
+    pub count: u64,
+    /// Sum of the amounts, zero for operations that carry none.
+    pub total_amount: Uint256,
+}
+
+/// A rollup of a batch by operation and denom, see [`summarize_batch`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchSummary(pub BTreeMap<(TokenFactoryOp, String), OpTotals>);
+
+impl BatchSummary {
+    /// Returns the totals of `op` on `denom`, or zeros if the batch has none.
+    pub fn get(&self, op: TokenFactoryOp, denom: &str) -> OpTotals {
+        self.0
+            .get(&(op, denom.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Groups the messages by operation and target denom, counting them and summing their
+/// amounts. CreateDenom is left out since its denom depends on the creator. Errors if a
+/// total overflows.
+pub fn summarize_batch(msgs: &[TokenFactoryMsg]) -> StdResult<BatchSummary> {
+    let mut summary = BatchSummary::default();
+    for msg in msgs {
+        let Some(denom) = msg.target_denom() else {
+            continue;
+        };
+        let amount = match msg {
+            TokenFactoryMsg::MintTokens { amount, .. }
+            | TokenFactoryMsg::BurnTokens { amount, .. }
+            | TokenFactoryMsg::ForceTransfer { amount, .. } => *amount,
+            _ => Uint256::zero(),
+        };
+        let totals = summary.0.entry((msg.op(), denom)).or_default();
+        totals.count += 1;
+        totals.total_amount = totals.total_amount.checked_add(amount)?;
+    }
+    Ok(summary)
+}
+
+/// Converts bank-style coins into one MintTokens per coin, all minted to `to`.
+/// Zero amounts are skipped.
+pub fn mint_coins(coins: Vec<Coin>, to: Addr) -> StdResult<Vec<TokenFactoryMsg>> {
+    Ok(coins
+        .into_iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .map(|coin| TokenFactoryMsg::MintTokens {
+            denom: coin.denom,
+            amount: coin.amount.into(),
+            mint_to_address: to.clone(),
+        })
+        .collect())
+}
+
+/// Returns true if every message is a token factory custom message.
+pub fn all_token_factory(msgs: &[CosmosMsg<TokenFactoryMsg>]) -> bool {
+    msgs.iter().all(|msg| matches!(msg, CosmosMsg::Custom(_)))
+}
+
+/// Returns the token factory messages in `msgs`, skipping any other kind of message.
+pub fn extract_token_factory(msgs: &[CosmosMsg<TokenFactoryMsg>]) -> Vec<&TokenFactoryMsg> {
+    msgs.iter()
+        .filter_map(|msg| match msg {
+            CosmosMsg::Custom(msg) => Some(msg),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sums the amounts minted of `denom` in `msgs` into a single coin, e.g. for an accounting
+/// event. Errors if the sum overflows or does not fit a `Uint128`.
+pub fn total_minted_coin(msgs: &[TokenFactoryMsg], denom: &str) -> Result<Coin, TokenFactoryError> {
+    let mut total = Uint256::zero();
+    for msg in msgs {
+        if let TokenFactoryMsg::MintTokens {
+            denom: minted,
+            amount,
+            ..
+        } = msg
+        {
+            if minted == denom {
+                total = total.checked_add(*amount)?;
+            }
+        }
+    }
+    Ok(Coin {
+        denom: denom.to_string(),
+        amount: Uint128::try_from(total)?,
+    })
+}
+
+/// Returns the funds the sender of `msgs` must hold for them to succeed: the creation fee
+/// under `params` once per CreateDenom. Other messages need no funds.
+pub fn required_funds_for_batch(
+    msgs: &[TokenFactoryMsg],
+    params: &TokenParams,
+) -> StdResult<Vec<Coin>> {
+    let creates = msgs
+        .iter()
+        .filter(|msg| matches!(msg, TokenFactoryMsg::CreateDenom { .. }))
+        .count() as u128;
+    if creates == 0 {
+        return Ok(vec![]);
+    }
+    params
+        .creation_fee_coins()?
+        .into_iter()
+        .map(|fee| {
+            Ok(Coin {
+                amount: fee.amount.checked_mul(Uint128::new(creates))?,
+                denom: fee.denom,
+            })
+        })
+        .collect()
+}
+
+/// Splits an airdrop into batches of at most `max_msgs_per_batch` mints, each mint wrapped in
+/// a submessage that replies on error, and returns one entry per batch. Mints of the n-th
+/// batch use reply id `reply_id + n`, so the reply handler can tell which batch failed. Submit
+/// one batch per transaction or response to keep each under the chain's gas limit.
+/// Errors on a zero amount before building any batch, since the chain rejects zero mints.
+pub fn airdrop_submsgs(
+    denom: String,
+    recipients: Vec<(Addr, Uint256)>,
+    max_msgs_per_batch: usize,
+    reply_id: u64,
+) -> StdResult<Vec<Vec<SubMsg<TokenFactoryMsg>>>> {
+    if max_msgs_per_batch == 0 {
+        return Err(TokenFactoryError::ZeroBatchSize {}.into());
+    }
+    if recipients.iter().any(|(_, amount)| amount.is_zero()) {
+        return Err(TokenFactoryError::ZeroAmount {}.into());
+    }
+    let mut batches = Vec::with_capacity(recipients.len().div_ceil(max_msgs_per_batch));
+    for (batch, chunk) in recipients.chunks(max_msgs_per_batch).enumerate() {
+        let id = reply_id
+            .checked_add(batch as u64)
+            .ok_or(TokenFactoryError::ReplyIdOverflow { reply_id })?;
+        let submsgs = chunk
+            .iter()
+            .map(|(recipient, amount)| {
+                let mint = TokenFactoryMsg::MintTokens {
+                    denom: denom.clone(),
+                    amount: *amount,
+                    mint_to_address: recipient.clone(),
+                };
+                SubMsg::reply_on_error(CosmosMsg::Custom(mint), id)
+            })
+            .collect();
+        batches.push(submsgs);
+    }
+    Ok(batches)
+}
+
+/// Wraps each message of a batch in a submessage that replies on error, all with `reply_id`,
+/// so a single reply handler catches a failure anywhere in the batch.
+///
+/// A submessage only wraps one message, so the batch is not atomic: when a message fails,
+/// only its own changes are reverted, and the messages after it still run if the reply
+/// handler returns Ok. Return an error from the handler to revert the whole batch.
+pub fn batch_into_submsg(
+    msgs: Vec<TokenFactoryMsg>,
+    reply_id: u64,
+) -> Vec<SubMsg<TokenFactoryMsg>> {
+    msgs.into_iter()
+        .map(|msg| SubMsg::reply_on_error(CosmosMsg::Custom(msg), reply_id))
+        .collect()
+}
+
+/// An ordering problem within a batch that would make a later message fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OrderHazard {
+    /// The admin of `denom` is changed at `change_admin_index`, but the message at
+    /// `later_index` still needs the contract to be the admin.
+    AdminChangedBeforeUse {
+        denom: String,
+        change_admin_index: usize,
+        later_index: usize,
+    },
+}
+
+/// Flags messages that need the contract to be a denom's admin but come after a ChangeAdmin
+/// (or, with `multi-admin`, a ChangeAdmins) of that denom in the same batch. Move the admin
+/// change last to fix them.
+pub fn detect_order_hazards(msgs: &[TokenFactoryMsg]) -> Vec<OrderHazard> {
+    let mut changed_at: BTreeMap<String, usize> = BTreeMap::new();
+    let mut hazards = vec![];
+    for (index, msg) in msgs.iter().enumerate() {
+        let Some(denom) = msg.target_denom() else {
+            continue;
+        };
+        if let Some(&change_admin_index) = changed_at.get(&denom) {
+            hazards.push(OrderHazard::AdminChangedBeforeUse {
+                denom: denom.clone(),
+                change_admin_index,
+                later_index: index,
+            });
+        }
+        if changes_admin(msg) {
+            changed_at.entry(denom).or_insert(index);
+        }
+    }
+    hazards
+}
+
+fn changes_admin(msg: &TokenFactoryMsg) -> bool {
+    match msg {
+        TokenFactoryMsg::ChangeAdmin { .. } => true,
+        #[cfg(feature = "multi-admin")]
+        TokenFactoryMsg::ChangeAdmins { .. } => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, BankMsg, ReplyOn};
+
+    use super::*;
+    use crate::fixtures;
+
+    fn mint(denom: &str, amount: u128) -> TokenFactoryMsg {
+        TokenFactoryMsg::MintTokens {
+            denom: denom.to_string(),
+            amount: Uint256::from(amount),
+            mint_to_address: Addr::unchecked("user"),
+        }
+    }
+
+    #[test]
+    fn batch_events_aggregate_mints_per_denom() {
+        let msgs = vec![
+            mint("factory/creator/b", 10),
+            mint("factory/creator/a", 5),
+            mint("factory/creator/b", 20),
+        ];
+        assert_eq!(
+            batch_events(&msgs).unwrap(),
+            vec![
+                Event::new("tf_mint_batch")
+                    .add_attribute("denom", "factory/creator/a")
+                    .add_attribute("count", "1")
+                    .add_attribute("total_amount", "5"),
+                Event::new("tf_mint_batch")
+                    .add_attribute("denom", "factory/creator/b")
+                    .add_attribute("count", "2")
+                    .add_attribute("total_amount", "30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_events_skip_messages_without_amount() {
+        let msgs = vec![TokenFactoryMsg::ChangeAdmin {
+            denom: "factory/creator/a".to_string(),
+            new_admin_address: Addr::unchecked("admin"),
+        }];
+        assert!(batch_events(&msgs).unwrap().is_empty());
+    }
+
+    #[test]
+    fn batch_events_error_on_overflow() {
+        let msgs = vec![
+            TokenFactoryMsg::BurnTokens {
+                denom: "factory/creator/a".to_string(),
+                amount: Uint256::MAX,
+                burn_from_address: Addr::unchecked("creator"),
+            };
+            2
+        ];
+        batch_events(&msgs).unwrap_err();
+    }
+
+    #[test]
+    fn mint_coins_skips_zero_amounts() {
+        let coins = vec![
+            coin(10, "factory/creator/a"),
+            coin(0, "factory/creator/b"),
+            coin(20, "factory/creator/c"),
+        ];
+        let msgs = mint_coins(coins, Addr::unchecked("user")).unwrap();
+        assert_eq!(
+            msgs,
+            vec![mint("factory/creator/a", 10), mint("factory/creator/c", 20)]
+        );
+    }
+
+    #[test]
+    fn all_token_factory_rejects_a_bank_message() {
+        let custom = CosmosMsg::Custom(mint("factory/creator/a", 10));
+        let bank = CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: vec![coin(1, "uosmo")],
+        });
+        assert!(all_token_factory(&[custom.clone(), custom.clone()]));
+        assert!(!all_token_factory(&[custom.clone(), bank.clone()]));
+        assert_eq!(
+            extract_token_factory(&[bank, custom]),
+            vec![&mint("factory/creator/a", 10)]
+        );
+    }
+
+    #[test]
+    fn total_minted_coin_sums_mints_of_the_denom() {
+        let msgs = vec![
+            mint("factory/creator/a", 10),
+            mint("factory/creator/b", 7),
+            mint("factory/creator/a", 5),
+        ];
+        assert_eq!(
+            total_minted_coin(&msgs, "factory/creator/a").unwrap(),
+            coin(15, "factory/creator/a")
+        );
+    }
+
+    #[test]
+    fn total_minted_coin_errors_beyond_uint128() {
+        let msgs = vec![
+            mint("factory/creator/a", u128::MAX),
+            mint("factory/creator/a", 1),
+        ];
+        total_minted_coin(&msgs, "factory/creator/a").unwrap_err();
+    }
+
+    fn recipients(count: u8) -> Vec<(Addr, Uint256)> {
+        (0..count)
+            .map(|i| (Addr::unchecked(format!("user{}", i)), Uint256::from(10u128)))
+            .collect()
+    }
+
+    #[test]
+    fn airdrop_submsgs_number_replies_per_batch() {
+        let batches =
+            airdrop_submsgs("factory/creator/a".to_string(), recipients(5), 2, 100).unwrap();
+        let ids: Vec<Vec<u64>> = batches
+            .iter()
+            .map(|batch| batch.iter().map(|submsg| submsg.id).collect())
+            .collect();
+        assert_eq!(ids, vec![vec![100, 100], vec![101, 101], vec![102]]);
+        assert!(batches
+            .iter()
+            .flatten()
+            .all(|submsg| submsg.reply_on == ReplyOn::Error));
+        assert_eq!(
+            batches[2][0].msg,
+            CosmosMsg::Custom(TokenFactoryMsg::MintTokens {
+                denom: "factory/creator/a".to_string(),
+                amount: Uint256::from(10u128),
+                mint_to_address: Addr::unchecked("user4"),
+            })
+        );
+    }
+
+    #[test]
+    fn airdrop_submsgs_reject_a_zero_amount() {
+        let mut recipients = recipients(3);
+        recipients[2].1 = Uint256::zero();
+        let err = airdrop_submsgs("factory/creator/a".to_string(), recipients, 2, 1).unwrap_err();
+        assert_eq!(err, TokenFactoryError::ZeroAmount {}.into());
+    }
+
+    #[test]
+    fn airdrop_submsgs_reject_a_zero_batch_size() {
+        let err =
+            airdrop_submsgs("factory/creator/a".to_string(), recipients(1), 0, 1).unwrap_err();
+        assert_eq!(err, TokenFactoryError::ZeroBatchSize {}.into());
+    }
+
+    #[test]
+    fn airdrop_submsgs_reject_overflowing_reply_ids() {
+        let err = airdrop_submsgs("factory/creator/a".to_string(), recipients(2), 1, u64::MAX)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::ReplyIdOverflow { reply_id: u64::MAX }.into()
+        );
+    }
+
+    fn change_admin(denom: &str) -> TokenFactoryMsg {
+        TokenFactoryMsg::ChangeAdmin {
+            denom: denom.to_string(),
+            new_admin_address: Addr::unchecked("admin"),
+        }
+    }
+
+    #[test]
+    fn detect_order_hazards_flags_use_after_admin_change() {
+        let msgs = vec![
+            change_admin("factory/creator/a"),
+            mint("factory/creator/b", 1),
+            mint("factory/creator/a", 1),
+        ];
+        assert_eq!(
+            detect_order_hazards(&msgs),
+            vec![OrderHazard::AdminChangedBeforeUse {
+                denom: "factory/creator/a".to_string(),
+                change_admin_index: 0,
+                later_index: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn detect_order_hazards_accepts_the_admin_change_last() {
+        let msgs = vec![
+            mint("factory/creator/a", 1),
+            mint("factory/creator/b", 1),
+            change_admin("factory/creator/a"),
+        ];
+        assert!(detect_order_hazards(&msgs).is_empty());
+    }
+
+    #[cfg(feature = "multi-admin")]
+    #[test]
+    fn detect_order_hazards_flags_use_after_admins_change() {
+        let msgs = vec![
+            TokenFactoryMsg::ChangeAdmins {
+                denom: "factory/creator/a".to_string(),
+                new_admins: vec![Addr::unchecked("admin")],
+            },
+            mint("factory/creator/a", 1),
+        ];
+        assert_eq!(detect_order_hazards(&msgs).len(), 1);
+    }
+
+    #[test]
+    fn summarize_batch_groups_by_op_and_denom() {
+        let msgs = vec![
+            mint("factory/creator/a", 10),
+            mint("factory/creator/a", 5),
+            mint("factory/creator/b", 1),
+            change_admin("factory/creator/a"),
+            TokenFactoryMsg::CreateDenom {
+                subdenom: "c".to_string(),
+                metadata: None,
+            },
+        ];
+        let summary = summarize_batch(&msgs).unwrap();
+        assert_eq!(summary.0.len(), 3);
+        assert_eq!(
+            summary.get(TokenFactoryOp::Mint, "factory/creator/a"),
+            OpTotals {
+                count: 2,
+                total_amount: Uint256::from(15u128),
+            }
+        );
+        assert_eq!(
+            summary.get(TokenFactoryOp::ChangeAdmin, "factory/creator/a"),
+            OpTotals {
+                count: 1,
+                total_amount: Uint256::zero(),
+            }
+        );
+        assert_eq!(
+            summary.get(TokenFactoryOp::Burn, "factory/creator/a"),
+            OpTotals::default()
+        );
+    }
+
+    #[test]
+    fn summarize_batch_errors_on_overflow() {
+        let msgs = vec![
+            TokenFactoryMsg::MintTokens {
+                denom: "factory/creator/a".to_string(),
+                amount: Uint256::MAX,
+                mint_to_address: Addr::unchecked("user"),
+            },
+            mint("factory/creator/a", 1),
+        ];
+        summarize_batch(&msgs).unwrap_err();
+    }
+
+    #[test]
+    fn required_funds_charge_the_fee_once_per_create() {
+        let create = |subdenom: &str| TokenFactoryMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+            metadata: None,
+        };
+        let msgs = vec![create("a"), mint("factory/creator/a", 10), create("b")];
+        assert_eq!(
+            required_funds_for_batch(&msgs, &fixtures::params()).unwrap(),
+            vec![coin(20_000_000, "uosmo")]
+        );
+        assert!(required_funds_for_batch(&msgs[1..2], &fixtures::params())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn batch_into_submsg_replies_on_error_with_one_id() {
+        let msgs = vec![mint("factory/creator/a", 1), mint("factory/creator/b", 2)];
+        let submsgs = batch_into_submsg(msgs.clone(), 7);
+        assert_eq!(
+            submsgs,
+            msgs.into_iter()
+                .map(|msg| SubMsg::reply_on_error(CosmosMsg::Custom(msg), 7))
+                .collect::<Vec<_>>()
+        );
+        assert!(submsgs
+            .iter()
+            .all(|submsg| submsg.id == 7 && submsg.reply_on == ReplyOn::Error));
+    }
+}
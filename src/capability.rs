@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+
+use crate::TokenFactoryMsg;
+
+/// Capability every token factory message needs, matching the `requires_token_factory` export.
+pub const CAPABILITY_TOKEN_FACTORY: &str = "token_factory";
+/// Capability needed by ForceTransfer.
+pub const CAPABILITY_FORCE_TRANSFER: &str = "token_factory_force_transfer";
+/// Capability needed by SetBeforeSendHook.
+pub const CAPABILITY_BEFORE_SEND_HOOK: &str = "token_factory_before_send_hook";
+/// Capability needed by ChangeAdmins.
+#[cfg(feature = "multi-admin")]
+pub const CAPABILITY_MULTI_ADMIN: &str = "token_factory_multi_admin";
+
+impl TokenFactoryMsg {
+    /// Returns the capability tags the chain must support for the message to succeed.
+    pub fn required_capabilities(&self) -> &'static [&'static str] {
+        match self {
+            TokenFactoryMsg::CreateDenom { .. }
+            | TokenFactoryMsg::ChangeAdmin { .. }
+            | TokenFactoryMsg::MintTokens { .. }
+            | TokenFactoryMsg::BurnTokens { .. }
+            | TokenFactoryMsg::SetMetadata { .. } => &[CAPABILITY_TOKEN_FACTORY],
+            TokenFactoryMsg::ForceTransfer { .. } => {
+                &[CAPABILITY_TOKEN_FACTORY, CAPABILITY_FORCE_TRANSFER]
+            }
+            TokenFactoryMsg::SetBeforeSendHook { .. } => {
+                &[CAPABILITY_TOKEN_FACTORY, CAPABILITY_BEFORE_SEND_HOOK]
+            }
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { .. } => {
+                &[CAPABILITY_TOKEN_FACTORY, CAPABILITY_MULTI_ADMIN]
+            }
+        }
+    }
+}
+
+/// Returns every capability tag the messages need, so deployment tooling can check that the
+/// target chain supports all of them before submitting.
+pub fn required_capability_set(msgs: &[TokenFactoryMsg]) -> BTreeSet<&'static str> {
+    msgs.iter()
+        .flat_map(|msg| msg.required_capabilities().iter().copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn force_transfer_and_hook_need_their_own_capability() {
+        assert_eq!(
+            fixtures::mint_tokens().required_capabilities(),
+            &[CAPABILITY_TOKEN_FACTORY]
+        );
+        assert_eq!(
+            fixtures::force_transfer().required_capabilities(),
+            &[CAPABILITY_TOKEN_FACTORY, CAPABILITY_FORCE_TRANSFER]
+        );
+        assert_eq!(
+            fixtures::set_before_send_hook().required_capabilities(),
+            &[CAPABILITY_TOKEN_FACTORY, CAPABILITY_BEFORE_SEND_HOOK]
+        );
+    }
+
+    #[test]
+    fn capability_set_merges_the_messages() {
+        let msgs = [
+            fixtures::mint_tokens(),
+            fixtures::force_transfer(),
+            fixtures::set_before_send_hook(),
+            fixtures::burn_tokens(),
+        ];
+        assert_eq!(
+            required_capability_set(&msgs),
+            BTreeSet::from([
+                CAPABILITY_TOKEN_FACTORY,
+                CAPABILITY_FORCE_TRANSFER,
+                CAPABILITY_BEFORE_SEND_HOOK,
+            ])
+        );
+    }
+}
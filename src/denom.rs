@@ -0,0 +1,282 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+
+use crate::{DenomMetadata, TokenFactoryError, TokenParams};
+
+/// Prefix shared by all token factory denoms.
+pub const FACTORY_DENOM_PREFIX: &str = "factory";
+
+/// Maximum length of a subdenom accepted by the token factory module.
+pub const MAX_SUBDENOM_LENGTH: usize = 44;
+
+/// A subdenom that passed validation. Deserializing one validates it too.
+#[cw_serde]
+#[serde(try_from = "String")]
+pub struct Subdenom(String);
+
+impl Subdenom {
+    /// Validates a subdenom against the chain's rule: non-empty, at most 44 characters,
+    /// in [0-9a-zA-Z./].
+    pub fn new(subdenom: impl Into<String>) -> Result<Self, TokenFactoryError> {
+        Subdenom::with_max_length(subdenom.into(), MAX_SUBDENOM_LENGTH)
+    }
+
+    /// Like [`Subdenom::new`], but with the maximum length reported in `params`, if any.
+    pub fn new_for_params(
+        subdenom: impl Into<String>,
+        params: &TokenParams,
+    ) -> Result<Self, TokenFactoryError> {
+        let max_length = params
+            .max_subdenom_length
+            .map_or(MAX_SUBDENOM_LENGTH, |length| length as usize);
+        Subdenom::with_max_length(subdenom.into(), max_length)
+    }
+
+    fn with_max_length(subdenom: String, max_length: usize) -> Result<Self, TokenFactoryError> {
+        let invalid = |reason: &str| TokenFactoryError::InvalidSubdenom {
+            subdenom: subdenom.clone(),
+            reason: reason.to_string(),
+        };
+        if subdenom.is_empty() {
+            return Err(invalid("cannot be empty"));
+        }
+        if subdenom.len() > max_length {
+            return Err(invalid("too long"));
+        }
+        if !subdenom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '/')
+        {
+            return Err(invalid("must only contain [0-9a-zA-Z./]"));
+        }
+        Ok(Subdenom(subdenom))
+    }
+
+    /// Like [`Subdenom::new`], but also rejects subdenoms the chain accepts yet are awkward
+    /// to work with: a leading or trailing `.` or `/`, or two separators in a row.
+    /// This is an opt-in convention, not a chain rule.
+    pub fn new_normalized(subdenom: impl Into<String>) -> Result<Self, TokenFactoryError> {
+        let subdenom = Subdenom::new(subdenom)?;
+        let is_separator = |c: char| c == '.' || c == '/';
+        let edge = subdenom.0.starts_with(is_separator) || subdenom.0.ends_with(is_separator);
+        let consecutive = subdenom
+            .0
+            .as_bytes()
+            .windows(2)
+            .any(|pair| is_separator(pair[0] as char) && is_separator(pair[1] as char));
+        if edge || consecutive {
+            return Err(TokenFactoryError::InvalidSubdenom {
+                subdenom: subdenom.0,
+                reason: "separators must not lead, trail or repeat".to_string(),
+            });
+        }
+        Ok(subdenom)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl TryFrom<String> for Subdenom {
+    type Error = TokenFactoryError;
+
+    fn try_from(subdenom: String) -> Result<Self, Self::Error> {
+        Subdenom::new(subdenom)
+    }
+}
+
+impl fmt::Display for Subdenom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Returns true if every subdenom `{prefix}{index}` with `index` up to `max_index` stays
+/// within `limit` characters, e.g. [`MAX_SUBDENOM_LENGTH`]. Only the length is checked.
+pub fn subdenom_fits(prefix: &str, max_index: u64, limit: usize) -> bool {
+    prefix.len() + max_index.to_string().len() <= limit
+}
+
+/// Builds the full denom a creator gets for a subdenom: factory/{creator}/{subdenom}
+pub fn full_denom(creator: &Addr, subdenom: &str) -> String {
+    format!("{}/{}/{}", FACTORY_DENOM_PREFIX, creator, subdenom)
+}
+
+/// Errors if the metadata base is not the full denom `creator` gets for `subdenom`.
+pub(crate) fn check_metadata_base(
+    creator: &Addr,
+    subdenom: &str,
+    metadata: &DenomMetadata,
+) -> Result<(), TokenFactoryError> {
+    let expected = full_denom(creator, subdenom);
+    if metadata.base != expected {
+        return Err(TokenFactoryError::MetadataBaseMismatch {
+            expected,
+            actual: metadata.base.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Splits a full factory denom into its creator and subdenom.
+/// Returns None if the denom is not of the form factory/{creator}/{subdenom}.
+/// The subdenom may itself contain slashes.
+pub fn parse_full_denom(denom: &str) -> Option<(&str, &str)> {
+    let mut parts = denom.splitn(3, '/');
+    if parts.next()? != FACTORY_DENOM_PREFIX {
+        return None;
+    }
+    let creator = parts.next()?;
+    let subdenom = parts.next()?;
+    if creator.is_empty() || subdenom.is_empty() {
+        return None;
+    }
+    Some((creator, subdenom))
+}
+
+/// Returns the denom with surrounding whitespace removed, as picked up from config files or
+/// query strings. Denoms are case-sensitive, so the case is kept.
+pub fn canonicalize_denom(denom: &str) -> String {
+    denom.trim().to_string()
+}
+
+/// Returns true if both denoms are the same once canonicalized, see [`canonicalize_denom`].
+pub fn denoms_equal(a: &str, b: &str) -> bool {
+    a.trim() == b.trim()
+}
+
+/// Checks a denom against the bank module's denom rules: 3 to 128 characters,
+/// starting with a letter, followed by letters, digits or one of `/:._-`.
+pub(crate) fn is_valid_denom(denom: &str) -> bool {
+    let mut chars = denom.chars();
+    (3..=128).contains(&denom.len())
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
+
+/// Checks that building the full denom for `creator` and `subdenom` and parsing it back
+/// recovers both unchanged.
+pub fn assert_denom_roundtrip(creator: &Addr, subdenom: &str) -> Result<(), TokenFactoryError> {
+    let denom = full_denom(creator, subdenom);
+    match parse_full_denom(&denom) {
+        Some((parsed_creator, parsed_subdenom))
+            if parsed_creator == creator.as_str() && parsed_subdenom == subdenom =>
+        {
+            Ok(())
+        }
+        _ => Err(TokenFactoryError::InvalidSubdenom {
+            subdenom: subdenom.to_string(),
+            reason: format!("{} does not parse back to its creator and subdenom", denom),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::from_json;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn metadata_base_matching_the_denom_passes() {
+        check_metadata_base(
+            &fixtures::creator(),
+            fixtures::SUBDENOM,
+            &fixtures::metadata(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn metadata_base_of_another_denom_fails() {
+        let err =
+            check_metadata_base(&fixtures::creator(), "other", &fixtures::metadata()).unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::MetadataBaseMismatch {
+                expected: full_denom(&fixtures::creator(), "other"),
+                actual: fixtures::denom(),
+            }
+        );
+    }
+
+    #[test]
+    fn normalized_subdenom_rejects_awkward_separators() {
+        for subdenom in ["/lp", "lp/", ".lp", "lp.", "lp//atom", "lp./atom"] {
+            let err = Subdenom::new_normalized(subdenom).unwrap_err();
+            assert!(
+                matches!(err, TokenFactoryError::InvalidSubdenom { .. }),
+                "{}",
+                subdenom
+            );
+        }
+    }
+
+    #[test]
+    fn normalized_subdenom_accepts_a_clean_name() {
+        let subdenom = Subdenom::new_normalized("lp/atom.osmo").unwrap();
+        assert_eq!(subdenom.as_str(), "lp/atom.osmo");
+    }
+
+    #[test]
+    fn deserializing_a_subdenom_validates_it() {
+        let subdenom: Subdenom = from_json(br#""uusdx""#).unwrap();
+        assert_eq!(subdenom.as_str(), "uusdx");
+        from_json::<Subdenom>(br#""u usdx""#).unwrap_err();
+    }
+
+    #[test]
+    fn denom_roundtrips_for_plain_and_nested_subdenoms() {
+        assert_denom_roundtrip(&fixtures::creator(), fixtures::SUBDENOM).unwrap();
+        assert_denom_roundtrip(&fixtures::creator(), "lp/atom/osmo").unwrap();
+    }
+
+    #[test]
+    fn denom_does_not_roundtrip_for_an_empty_subdenom_or_a_slashed_creator() {
+        assert_denom_roundtrip(&fixtures::creator(), "").unwrap_err();
+        assert_denom_roundtrip(&Addr::unchecked("osmo1/abc"), fixtures::SUBDENOM).unwrap_err();
+    }
+
+    #[test]
+    fn subdenom_fits_checks_the_longest_index() {
+        assert!(subdenom_fits("ticket", 99, 8));
+        assert!(!subdenom_fits("ticket", 100, 8));
+        assert!(subdenom_fits(&"x".repeat(43), 9, MAX_SUBDENOM_LENGTH));
+        assert!(!subdenom_fits(&"x".repeat(43), 10, MAX_SUBDENOM_LENGTH));
+    }
+
+    #[test]
+    fn denoms_compare_equal_ignoring_surrounding_whitespace_only() {
+        assert_eq!(
+            canonicalize_denom("  factory/osmo1abc/uusdx\n"),
+            "factory/osmo1abc/uusdx"
+        );
+        assert!(denoms_equal(" uosmo", "uosmo\t"));
+        assert!(!denoms_equal("uosmo", "UOSMO"));
+    }
+
+    #[test]
+    fn params_without_max_length_use_the_default() {
+        let params: TokenParams = from_json(br#"{"denom_creation_fee":[]}"#).unwrap();
+        assert_eq!(params.max_subdenom_length, None);
+        Subdenom::new_for_params("x".repeat(MAX_SUBDENOM_LENGTH), &params).unwrap();
+        Subdenom::new_for_params("x".repeat(MAX_SUBDENOM_LENGTH + 1), &params).unwrap_err();
+    }
+
+    #[test]
+    fn params_with_max_length_override_the_default() {
+        let params: TokenParams =
+            from_json(br#"{"denom_creation_fee":[],"max_subdenom_length":8}"#).unwrap();
+        assert_eq!(params.max_subdenom_length, Some(8));
+        Subdenom::new_for_params("ticket99", &params).unwrap();
+        Subdenom::new_for_params("ticket100", &params).unwrap_err();
+    }
+}
@@ -0,0 +1,54 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, StdResult};
+
+use crate::TokenFactoryMsg;
+
+/// Route under which routed chains dispatch token factory messages.
+pub const TOKEN_FACTORY_ROUTE: &str = "tokenfactory";
+
+/// Outer wrapper for chains whose bindings route custom messages to a module as
+/// `{"route": "tokenfactory", "msg_data": {...}}`, with `msg_data` in the format of this crate.
+/// Chains that take the custom message directly (e.g. Osmosis-style bindings) do not need it.
+/// Chains with their own message format under a route, such as Injective, do not accept it.
+#[cw_serde]
+pub struct TokenFactoryEnvelope {
+    pub route: String,
+    pub msg_data: TokenFactoryMsg,
+}
+
+impl From<TokenFactoryMsg> for TokenFactoryEnvelope {
+    fn from(msg_data: TokenFactoryMsg) -> Self {
+        TokenFactoryEnvelope {
+            route: TOKEN_FACTORY_ROUTE.to_string(),
+            msg_data,
+        }
+    }
+}
+
+impl TokenFactoryMsg {
+    /// Serializes the message wrapped in the routed chain envelope.
+    pub fn to_chain_envelope(&self) -> StdResult<Binary> {
+        to_json_binary(&TokenFactoryEnvelope::from(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Addr, Uint256};
+
+    use super::*;
+
+    #[test]
+    fn envelope_wraps_the_message_under_the_route() {
+        let msg = TokenFactoryMsg::MintTokens {
+            denom: "factory/creator/uusdx".to_string(),
+            amount: Uint256::from(5u128),
+            mint_to_address: Addr::unchecked("user"),
+        };
+        let expected = r#"{"route":"tokenfactory","msg_data":{"mint_tokens":{"denom":"factory/creator/uusdx","amount":"5","mint_to_address":"user"}}}"#;
+        assert_eq!(
+            msg.to_chain_envelope().unwrap().as_slice(),
+            expected.as_bytes()
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use cosmwasm_std::{Coin, ConversionOverflowError, OverflowError, StdError};
+use thiserror::Error;
+
+/// Errors returned by the validation and helper functions of this crate.
+#[derive(Error, Debug, PartialEq)]
+pub enum TokenFactoryError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("Invalid denom {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Amount must be nonzero")]
+    ZeroAmount {},
+
+    #[error("Minting to the contract itself ({address}) instead of a user")]
+    MintToSelf { address: String },
+
+    #[error("Force transfer from {address} to itself")]
+    ForceTransferToSelf { address: String },
+
+    #[error("Invalid subdenom {subdenom}: {reason}")]
+    InvalidSubdenom { subdenom: String, reason: String },
+
+    #[error("Invalid metadata: {reason}")]
+    InvalidMetadata { reason: String },
+
+    #[error("Metadata is not exchange ready: {reason}")]
+    NotExchangeReady { reason: String },
+
+    #[error("Alias {alias} is used by more than one denom unit")]
+    ConflictingAlias { alias: String },
+
+    #[error("Address {address} is frozen")]
+    Frozen { address: String },
+
+    #[error("Address {address} looks like the {module} module account")]
+    ModuleAccountSource { address: String, module: String },
+
+    #[error("Invalid token spec {spec}: {reason}")]
+    InvalidSpec { spec: String, reason: String },
+
+    #[error("Invalid metadata patch: {reason}")]
+    InvalidMetadataPatch { reason: String },
+
+    #[error("Invalid token factory message: {reason}")]
+    InvalidMsgJson { reason: String },
+
+    /// `actual` is None if the denom has no metadata or its display unit is not listed.
+    #[error(
+        "Expected {expected} decimals, found {}",
+        actual.map_or("no display unit".to_string(), |actual| actual.to_string())
+    )]
+    DecimalsMismatch { expected: u32, actual: Option<u32> },
+
+    #[error("Denom {denom} does not exist")]
+    DenomNotFound { denom: String },
+
+    #[error(
+        "Insufficient funds: needed {}, attached {}",
+        coins_to_string(needed),
+        coins_to_string(attached)
+    )]
+    InsufficientFunds {
+        needed: Vec<Coin>,
+        attached: Vec<Coin>,
+    },
+
+    #[error("Excess funds: {} more than needed", coins_to_string(excess))]
+    ExcessFunds { excess: Vec<Coin> },
+
+    #[error("Metadata base {actual} does not match denom {expected}")]
+    MetadataBaseMismatch { expected: String, actual: String },
+
+    #[error("Batch size must be nonzero")]
+    ZeroBatchSize {},
+
+    #[error("Reply ids starting at {reply_id} overflow")]
+    ReplyIdOverflow { reply_id: u64 },
+
+    #[error("Before-send hook address cannot be empty")]
+    EmptyHook {},
+
+    #[error("Neither {denom} nor template {template} has metadata")]
+    NoMetadataToInherit { denom: String, template: String },
+
+    #[error("Create denom failed: {reason}")]
+    CreateDenomFailed { reason: String },
+
+    #[error(
+        "Create denom reply has no {} attribute",
+        crate::NEW_TOKEN_DENOM_ATTRIBUTE
+    )]
+    NoCreatedDenom {},
+}
+
+impl From<TokenFactoryError> for StdError {
+    fn from(err: TokenFactoryError) -> Self {
+        match err {
+            TokenFactoryError::Std(err) => err,
+            TokenFactoryError::Overflow(err) => StdError::overflow(err),
+            TokenFactoryError::ConversionOverflow(err) => err.into(),
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+fn coins_to_string(coins: &[Coin]) -> String {
+    if coins.is_empty() {
+        return "nothing".to_string();
+    }
+    coins
+        .iter()
+        .map(Coin::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
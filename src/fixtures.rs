@@ -0,0 +1,169 @@
+use cosmwasm_std::{Addr, Uint256};
+
+use crate::{full_denom, DenomCreationFee, DenomMetadata, DenomUnit, TokenFactoryMsg, TokenParams};
+
+/// Subdenom of the fixture denom.
+pub const SUBDENOM: &str = "uusdx";
+
+/// The contract creating the fixture denom.
+pub fn creator() -> Addr {
+    Addr::unchecked("osmo19qn7zx5302f0yvhwud59jrheqs9z77x925yex8hllkqkn40gazvq3w2tml")
+}
+
+/// A user account receiving and holding tokens.
+pub fn user() -> Addr {
+    Addr::unchecked("osmo1agjy2lp2x9lltadtk6nhu8zd7yw6u8te96ye7j")
+}
+
+/// An account taking over the admin of the fixture denom.
+pub fn new_admin() -> Addr {
+    Addr::unchecked("osmo1kzfs4qky9z50evtxtajmf60jte5f48fmejld4h")
+}
+
+/// A before-send hook contract.
+pub fn hook() -> Addr {
+    Addr::unchecked("osmo17f8q700qxprp5tcxaa9vqnmtlz8rw8m008emmwgahkl39ege9geslgg6m9")
+}
+
+/// The full denom [`creator`] gets for [`SUBDENOM`].
+pub fn denom() -> String {
+    full_denom(&creator(), SUBDENOM)
+}
+
+/// Metadata of the fixture denom: a stablecoin-like token with 6 decimals.
+pub fn metadata() -> DenomMetadata {
+    DenomMetadata {
+        description: "An example USD stablecoin".to_string(),
+        denom_units: vec![
+            DenomUnit {
+                denom: denom(),
+                exponent: 0,
+                aliases: vec!["microusdx".to_string()],
+            },
+            DenomUnit {
+                denom: "usdx".to_string(),
+                exponent: 6,
+                aliases: vec![],
+            },
+        ],
+        base: denom(),
+        display: "usdx".to_string(),
+        name: "USD Example".to_string(),
+        symbol: "USDX".to_string(),
+    }
+}
+
+/// Params charging 10 OSMO and some extra gas per denom creation.
+pub fn params() -> TokenParams {
+    TokenParams {
+        denom_creation_fee: vec![DenomCreationFee {
+            amount: Uint256::from(10_000_000u128),
+            denom: "uosmo".to_string(),
+        }],
+        denom_creation_gas_consume: Some(1_000_000),
+        max_subdenom_length: None,
+    }
+}
+
+pub fn create_denom() -> TokenFactoryMsg {
+    TokenFactoryMsg::CreateDenom {
+        subdenom: SUBDENOM.to_string(),
+        metadata: Some(metadata()),
+    }
+}
+
+pub fn change_admin() -> TokenFactoryMsg {
+    TokenFactoryMsg::ChangeAdmin {
+        denom: denom(),
+        new_admin_address: new_admin(),
+    }
+}
+
+pub fn mint_tokens() -> TokenFactoryMsg {
+    TokenFactoryMsg::MintTokens {
+        denom: denom(),
+        amount: Uint256::from(1_000_000u128),
+        mint_to_address: user(),
+    }
+}
+
+pub fn burn_tokens() -> TokenFactoryMsg {
+    TokenFactoryMsg::BurnTokens {
+        denom: denom(),
+        amount: Uint256::from(500_000u128),
+        burn_from_address: creator(),
+    }
+}
+
+pub fn set_metadata() -> TokenFactoryMsg {
+    TokenFactoryMsg::SetMetadata {
+        metadata: metadata(),
+    }
+}
+
+pub fn force_transfer() -> TokenFactoryMsg {
+    TokenFactoryMsg::ForceTransfer {
+        denom: denom(),
+        from_address: user(),
+        to_address: creator(),
+        amount: Uint256::from(250_000u128),
+    }
+}
+
+pub fn set_before_send_hook() -> TokenFactoryMsg {
+    TokenFactoryMsg::SetBeforeSendHook {
+        denom: denom(),
+        contract_addr: hook(),
+    }
+}
+
+#[cfg(feature = "multi-admin")]
+pub fn change_admins() -> TokenFactoryMsg {
+    TokenFactoryMsg::ChangeAdmins {
+        denom: denom(),
+        new_admins: vec![creator(), new_admin()],
+    }
+}
+
+/// One message of each variant, in declaration order.
+pub fn all_messages() -> Vec<TokenFactoryMsg> {
+    vec![
+        create_denom(),
+        change_admin(),
+        mint_tokens(),
+        burn_tokens(),
+        set_metadata(),
+        force_transfer(),
+        set_before_send_hook(),
+        #[cfg(feature = "multi-admin")]
+        change_admins(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coin;
+
+    use super::*;
+    use crate::Subdenom;
+
+    #[test]
+    fn messages_pass_validate() {
+        for msg in all_messages() {
+            msg.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn metadata_is_exchange_ready_for_the_fixture_denom() {
+        metadata().validate_exchange_ready().unwrap();
+        assert_eq!(metadata().base, denom());
+        Subdenom::new_for_params(SUBDENOM, &params()).unwrap();
+    }
+
+    #[test]
+    fn params_charge_a_fee() {
+        let fees = params().creation_fee_coins().unwrap();
+        assert_eq!(fees, vec![coin(10_000_000, "uosmo")]);
+    }
+}
@@ -0,0 +1,651 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Response, StdResult, Uint128, Uint256};
+
+use crate::denom::check_metadata_base;
+use crate::{
+    full_denom, DenomMetadata, DenomUnit, Subdenom, TokenFactoryError, TokenFactoryMsg, TokenParams,
+};
+
+/// Builds all messages of a token launch in one go: CreateDenom, SetBeforeSendHook, the
+/// initial mints and, last so that it does not break the messages before it, ChangeAdmin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenLaunch {
+    subdenom: String,
+    metadata: Option<DenomMetadata>,
+    hook: Option<Addr>,
+    mints: Vec<(Addr, Uint256)>,
+    final_admin: Option<Addr>,
+}
+
+impl TokenLaunch {
+    pub fn new(subdenom: impl Into<String>) -> Self {
+        TokenLaunch {
+            subdenom: subdenom.into(),
+            metadata: None,
+            hook: None,
+            mints: vec![],
+            final_admin: None,
+        }
+    }
+
+    /// Sets the metadata to create the denom with. Its base must be the created denom.
+    pub fn metadata(mut self, metadata: DenomMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Sets the before-send hook right after creation.
+    pub fn before_send_hook(mut self, hook: Addr) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Adds a mint of `amount` to `recipient` to the initial distribution.
+    pub fn mint(mut self, recipient: Addr, amount: Uint256) -> Self {
+        self.mints.push((recipient, amount));
+        self
+    }
+
+    /// Hands the admin over to `admin` once everything else is done.
+    /// An empty address revokes the admin.
+    pub fn final_admin(mut self, admin: Addr) -> Self {
+        self.final_admin = Some(admin);
+        self
+    }
+
+    /// Returns the launch messages for a denom created by `creator`, each validated.
+    pub fn messages(&self, creator: &Addr) -> StdResult<Vec<TokenFactoryMsg>> {
+        if let Some(metadata) = &self.metadata {
+            check_metadata_base(creator, &self.subdenom, metadata)?;
+        }
+        let denom = full_denom(creator, &self.subdenom);
+        let mut msgs = vec![TokenFactoryMsg::CreateDenom {
+            subdenom: self.subdenom.clone(),
+            metadata: self.metadata.clone(),
+        }];
+        if let Some(hook) = &self.hook {
+            msgs.push(TokenFactoryMsg::SetBeforeSendHook {
+                denom: denom.clone(),
+                contract_addr: hook.clone(),
+            });
+        }
+        msgs.extend(
+            self.mints
+                .iter()
+                .map(|(recipient, amount)| TokenFactoryMsg::MintTokens {
+                    denom: denom.clone(),
+                    amount: *amount,
+                    mint_to_address: recipient.clone(),
+                }),
+        );
+        if let Some(admin) = &self.final_admin {
+            msgs.push(TokenFactoryMsg::ChangeAdmin {
+                denom,
+                new_admin_address: admin.clone(),
+            });
+        }
+        for msg in &msgs {
+            msg.validate()?;
+        }
+        Ok(msgs)
+    }
+
+    /// Returns a response carrying the launch messages, see [`TokenLaunch::messages`].
+    pub fn build(&self, creator: &Addr) -> StdResult<Response<TokenFactoryMsg>> {
+        Ok(
+            Response::new()
+                .add_messages(self.messages(creator)?.into_iter().map(CosmosMsg::Custom)),
+        )
+    }
+
+    /// Returns what the launch would do, for a confirmation screen. Errors wherever
+    /// [`TokenLaunch::messages`] does, or if the mint total or the fee overflows.
+    pub fn estimate(&self, creator: &Addr, params: &TokenParams) -> StdResult<LaunchEstimate> {
+        let msgs = self.messages(creator)?;
+        let mut total_mint = Uint256::zero();
+        for (_, amount) in &self.mints {
+            total_mint = total_mint.checked_add(*amount)?;
+        }
+        Ok(LaunchEstimate {
+            message_count: msgs.len() as u64,
+            total_mint,
+            funds: params.creation_fee_coins()?,
+        })
+    }
+
+    /// Like [`TokenLaunch::build`], also returning the creation fee under `params`, which
+    /// `creator` must hold when the response executes.
+    pub fn build_with_fee(
+        &self,
+        creator: &Addr,
+        params: &TokenParams,
+    ) -> StdResult<(Response<TokenFactoryMsg>, Vec<Coin>)> {
+        Ok((self.build(creator)?, params.creation_fee_coins()?))
+    }
+}
+
+/// The outcome of a [`TokenLaunch`], see [`TokenLaunch::estimate`].
+#[cw_serde]
+pub struct LaunchEstimate {
+    /// Number of messages the launch sends.
+    pub message_count: u64,
+    /// Sum of the initial mints.
+    pub total_mint: Uint256,
+    /// Creation fee the creator must hold.
+    pub funds: Vec<Coin>,
+}
+
+/// A token launch described as data, e.g. loaded from a JSON deployment config.
+/// See [`TokenSpec::into_messages`].
+#[cw_serde]
+pub struct TokenSpec {
+    pub subdenom: String,
+    /// Metadata to create the denom with. Its base must be the created denom.
+    pub metadata: Option<DenomMetadata>,
+    /// Mints performed right after creation, in order.
+    #[serde(default)]
+    pub initial_distribution: Vec<InitialMint>,
+    /// Admin handed over to once the launch is done. None keeps the creator as admin
+    /// and an empty address revokes the admin.
+    pub final_admin: Option<Addr>,
+    pub before_send_hook: Option<Addr>,
+}
+
+/// A mint of the initial distribution of a [`TokenSpec`].
+#[cw_serde]
+pub struct InitialMint {
+    pub recipient: Addr,
+    pub amount: Uint256,
+}
+
+impl TokenSpec {
+    /// Returns the launch messages for a denom created by `creator`, in the order of
+    /// [`TokenLaunch`], each validated.
+    pub fn into_messages(&self, creator: &Addr) -> StdResult<Vec<CosmosMsg<TokenFactoryMsg>>> {
+        Ok(self
+            .to_launch()
+            .messages(creator)?
+            .into_iter()
+            .map(CosmosMsg::Custom)
+            .collect())
+    }
+
+    /// Returns the builder equivalent to the spec.
+    pub fn to_launch(&self) -> TokenLaunch {
+        let mut launch = TokenLaunch::new(self.subdenom.clone());
+        if let Some(metadata) = &self.metadata {
+            launch = launch.metadata(metadata.clone());
+        }
+        if let Some(hook) = &self.before_send_hook {
+            launch = launch.before_send_hook(hook.clone());
+        }
+        for mint in &self.initial_distribution {
+            launch = launch.mint(mint.recipient.clone(), mint.amount);
+        }
+        if let Some(admin) = &self.final_admin {
+            launch = launch.final_admin(admin.clone());
+        }
+        launch
+    }
+}
+
+/// The launch of a regulated token, whose transfers are checked by a before-send hook from
+/// the start. See [`RegulatedTokenLaunch::build`].
+#[cw_serde]
+pub struct RegulatedTokenLaunch {
+    pub subdenom: String,
+    /// Must pass [`DenomMetadata::validate_exchange_ready`] and have the created denom as base.
+    pub metadata: DenomMetadata,
+    pub hook: Addr,
+    /// Mints performed once the hook is in place, in order.
+    #[serde(default)]
+    pub initial_mints: Vec<InitialMint>,
+}
+
+impl RegulatedTokenLaunch {
+    /// Returns a response with, in order: CreateDenom, SetMetadata, SetBeforeSendHook and the
+    /// initial mints, so no tokens exist before the hook does. Errors if the hook address is
+    /// empty, the metadata is not exchange ready or any message is invalid.
+    pub fn build(&self, creator: &Addr) -> StdResult<Response<TokenFactoryMsg>> {
+        if self.hook.as_str().is_empty() {
+            return Err(TokenFactoryError::EmptyHook {}.into());
+        }
+        self.metadata.validate_exchange_ready()?;
+        let mut msgs = create_denom_with_hook(
+            self.subdenom.clone(),
+            Some(self.metadata.clone()),
+            self.hook.clone(),
+            creator,
+        )?;
+        let denom = full_denom(creator, &self.subdenom);
+        msgs.extend(self.initial_mints.iter().map(|mint| {
+            CosmosMsg::Custom(TokenFactoryMsg::MintTokens {
+                denom: denom.clone(),
+                amount: mint.amount,
+                mint_to_address: mint.recipient.clone(),
+            })
+        }));
+        for msg in &msgs {
+            if let CosmosMsg::Custom(msg) = msg {
+                msg.validate()?;
+            }
+        }
+        Ok(Response::new().add_messages(msgs))
+    }
+}
+
+/// Creates a denom and immediately sets its before-send hook, for regulated tokens whose
+/// transfers must be checked from the start. Produces, in order: CreateDenom, SetMetadata
+/// if `metadata` is given, and SetBeforeSendHook on the denom `creator` will get.
+pub fn create_denom_with_hook(
+    subdenom: String,
+    metadata: Option<DenomMetadata>,
+    hook: Addr,
+    creator: &Addr,
+) -> StdResult<Vec<CosmosMsg<TokenFactoryMsg>>> {
+    let denom = full_denom(creator, &subdenom);
+    let mut msgs = vec![CosmosMsg::Custom(TokenFactoryMsg::CreateDenom {
+        subdenom: subdenom.clone(),
+        metadata: None,
+    })];
+    if let Some(metadata) = metadata {
+        check_metadata_base(creator, &subdenom, &metadata)?;
+        msgs.push(CosmosMsg::Custom(TokenFactoryMsg::SetMetadata { metadata }));
+    }
+    msgs.push(CosmosMsg::Custom(TokenFactoryMsg::SetBeforeSendHook {
+        denom,
+        contract_addr: hook,
+    }));
+    Ok(msgs)
+}
+
+/// Builds a CreateDenom whose metadata base is derived from `creator` and `subdenom`, so it
+/// always matches the created denom. The display unit is the base followed by the lowercased
+/// symbol, e.g. `factory/osmo1.../uusdx/usdx`, with `decimals` as exponent, or the base itself
+/// if `decimals` is 0. Deriving it from the base keeps it a valid denom for short symbols and
+/// distinct from the display units of other denoms with the same symbol.
+pub fn create_with_derived_base(
+    subdenom: String,
+    name: String,
+    symbol: String,
+    decimals: u32,
+    creator: &Addr,
+) -> StdResult<TokenFactoryMsg> {
+    Subdenom::new(subdenom.as_str())?;
+    let base = full_denom(creator, &subdenom);
+    let mut denom_units = vec![DenomUnit {
+        denom: base.clone(),
+        exponent: 0,
+        aliases: vec![],
+    }];
+    let display = if decimals == 0 {
+        base.clone()
+    } else {
+        let display = format!("{}/{}", base, symbol.to_lowercase());
+        denom_units.push(DenomUnit {
+            denom: display.clone(),
+            exponent: decimals,
+            aliases: vec![],
+        });
+        display
+    };
+    let metadata = DenomMetadata {
+        description: String::new(),
+        denom_units,
+        base,
+        display,
+        name,
+        symbol,
+    };
+    metadata.validate()?;
+    Ok(TokenFactoryMsg::CreateDenom {
+        subdenom,
+        metadata: Some(metadata),
+    })
+}
+
+/// Checks that `attached` pays exactly the creation fee under `params`, e.g. the funds a user
+/// sent along to have the contract create a denom for them. Coins of the same denom are
+/// summed and zero coins ignored. Errors with [`TokenFactoryError::InsufficientFunds`] if
+/// any fee denom is short, otherwise with [`TokenFactoryError::ExcessFunds`] listing what
+/// was attached on top of the fee.
+pub fn check_creation_funds(
+    attached: &[Coin],
+    params: &TokenParams,
+) -> Result<(), TokenFactoryError> {
+    let needed = params.creation_fee_coins()?;
+    let mut remaining: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for coin in attached {
+        let total = remaining.entry(coin.denom.as_str()).or_default();
+        *total = total.checked_add(coin.amount)?;
+    }
+    let attached_total: Vec<Coin> = remaining
+        .iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(denom, amount)| Coin::new(amount.u128(), *denom))
+        .collect();
+
+    for fee in &needed {
+        let paid = remaining.entry(fee.denom.as_str()).or_default();
+        match paid.checked_sub(fee.amount) {
+            Ok(rest) => *paid = rest,
+            Err(_) => {
+                return Err(TokenFactoryError::InsufficientFunds {
+                    needed,
+                    attached: attached_total,
+                })
+            }
+        }
+    }
+    let excess: Vec<Coin> = remaining
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(denom, amount)| Coin::new(amount.u128(), denom))
+        .collect();
+    if !excess.is_empty() {
+        return Err(TokenFactoryError::ExcessFunds { excess });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::from_json;
+
+    use super::*;
+    use crate::{fixtures, DenomCreationFee};
+
+    fn bare_create() -> TokenFactoryMsg {
+        TokenFactoryMsg::CreateDenom {
+            subdenom: fixtures::SUBDENOM.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn create_denom_with_hook_sets_the_hook_after_creation() {
+        let msgs = create_denom_with_hook(
+            fixtures::SUBDENOM.to_string(),
+            None,
+            fixtures::hook(),
+            &fixtures::creator(),
+        )
+        .unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Custom(bare_create()),
+                CosmosMsg::Custom(fixtures::set_before_send_hook()),
+            ]
+        );
+    }
+
+    #[test]
+    fn create_denom_with_hook_sets_the_metadata_in_between() {
+        let msgs = create_denom_with_hook(
+            fixtures::SUBDENOM.to_string(),
+            Some(fixtures::metadata()),
+            fixtures::hook(),
+            &fixtures::creator(),
+        )
+        .unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Custom(bare_create()),
+                CosmosMsg::Custom(fixtures::set_metadata()),
+                CosmosMsg::Custom(fixtures::set_before_send_hook()),
+            ]
+        );
+    }
+
+    #[test]
+    fn create_denom_with_hook_rejects_metadata_of_another_denom() {
+        create_denom_with_hook(
+            "other".to_string(),
+            Some(fixtures::metadata()),
+            fixtures::hook(),
+            &fixtures::creator(),
+        )
+        .unwrap_err();
+    }
+
+    fn derived_metadata(symbol: &str, decimals: u32) -> DenomMetadata {
+        let msg = create_with_derived_base(
+            fixtures::SUBDENOM.to_string(),
+            "Example".to_string(),
+            symbol.to_string(),
+            decimals,
+            &fixtures::creator(),
+        )
+        .unwrap();
+        match msg {
+            TokenFactoryMsg::CreateDenom {
+                metadata: Some(metadata),
+                ..
+            } => metadata,
+            msg => panic!("unexpected message {:?}", msg),
+        }
+    }
+
+    #[test]
+    fn derived_base_is_the_created_denom() {
+        let metadata = derived_metadata("USDX", 6);
+        assert_eq!(metadata.base, fixtures::denom());
+        assert_eq!(metadata.display, format!("{}/usdx", fixtures::denom()));
+        assert_eq!(metadata.display_exponent(), Some(6));
+    }
+
+    #[test]
+    fn derived_display_is_valid_for_short_symbols() {
+        let metadata = derived_metadata("OP", 18);
+        assert_eq!(metadata.display, format!("{}/op", fixtures::denom()));
+        metadata.validate().unwrap();
+    }
+
+    #[test]
+    fn derived_display_is_the_base_without_decimals() {
+        let metadata = derived_metadata("PTS", 0);
+        assert_eq!(metadata.display, fixtures::denom());
+        assert_eq!(metadata.denom_units.len(), 1);
+    }
+
+    fn launch() -> TokenLaunch {
+        TokenLaunch::new(fixtures::SUBDENOM)
+            .metadata(fixtures::metadata())
+            .before_send_hook(fixtures::hook())
+            .mint(fixtures::user(), Uint256::from(1_000_000u128))
+            .final_admin(fixtures::new_admin())
+    }
+
+    #[test]
+    fn launch_messages_change_the_admin_last() {
+        let msgs = launch().messages(&fixtures::creator()).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                fixtures::create_denom(),
+                fixtures::set_before_send_hook(),
+                fixtures::mint_tokens(),
+                fixtures::change_admin(),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_with_fee_returns_the_creation_fee() {
+        let (response, funds) = launch()
+            .build_with_fee(&fixtures::creator(), &fixtures::params())
+            .unwrap();
+        assert_eq!(response.messages.len(), 4);
+        assert_eq!(funds, vec![Coin::new(10_000_000, "uosmo")]);
+    }
+
+    #[test]
+    fn build_with_fee_leaves_zero_fees_out() {
+        let mut params = fixtures::params();
+        params.denom_creation_fee.push(DenomCreationFee {
+            amount: Uint256::zero(),
+            denom: "uatom".to_string(),
+        });
+        let (_, funds) = launch()
+            .build_with_fee(&fixtures::creator(), &params)
+            .unwrap();
+        assert_eq!(funds, vec![Coin::new(10_000_000, "uosmo")]);
+    }
+
+    #[test]
+    fn token_spec_from_json_builds_the_launch_messages() {
+        let json = format!(
+            r#"{{
+                "subdenom": "{}",
+                "metadata": null,
+                "initial_distribution": [{{ "recipient": "{}", "amount": "1000000" }}],
+                "final_admin": "{}",
+                "before_send_hook": null
+            }}"#,
+            fixtures::SUBDENOM,
+            fixtures::user(),
+            fixtures::new_admin(),
+        );
+        let spec: TokenSpec = from_json(json.as_bytes()).unwrap();
+        assert_eq!(
+            spec.into_messages(&fixtures::creator()).unwrap(),
+            vec![
+                CosmosMsg::Custom(bare_create()),
+                CosmosMsg::Custom(fixtures::mint_tokens()),
+                CosmosMsg::Custom(fixtures::change_admin()),
+            ]
+        );
+    }
+
+    #[test]
+    fn token_spec_rejects_metadata_of_another_denom() {
+        let spec = TokenSpec {
+            subdenom: "other".to_string(),
+            metadata: Some(fixtures::metadata()),
+            initial_distribution: vec![],
+            final_admin: None,
+            before_send_hook: None,
+        };
+        spec.into_messages(&fixtures::creator()).unwrap_err();
+    }
+
+    #[test]
+    fn estimate_counts_messages_mints_and_fee() {
+        let estimate = TokenLaunch::new(fixtures::SUBDENOM)
+            .mint(fixtures::user(), Uint256::from(1_000_000u128))
+            .mint(fixtures::new_admin(), Uint256::from(500_000u128))
+            .final_admin(fixtures::new_admin())
+            .estimate(&fixtures::creator(), &fixtures::params())
+            .unwrap();
+        assert_eq!(
+            estimate,
+            LaunchEstimate {
+                message_count: 4,
+                total_mint: Uint256::from(1_500_000u128),
+                funds: vec![Coin::new(10_000_000, "uosmo")],
+            }
+        );
+    }
+
+    #[test]
+    fn estimate_errors_on_an_overflowing_mint_total() {
+        TokenLaunch::new(fixtures::SUBDENOM)
+            .mint(fixtures::user(), Uint256::MAX)
+            .mint(fixtures::new_admin(), Uint256::one())
+            .estimate(&fixtures::creator(), &fixtures::params())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn estimate_errors_where_messages_does() {
+        TokenLaunch::new(fixtures::SUBDENOM)
+            .mint(fixtures::user(), Uint256::zero())
+            .estimate(&fixtures::creator(), &fixtures::params())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn exact_creation_funds_pass() {
+        let attached = vec![Coin::new(4_000_000, "uosmo"), Coin::new(6_000_000, "uosmo")];
+        check_creation_funds(&attached, &fixtures::params()).unwrap();
+    }
+
+    #[test]
+    fn insufficient_creation_funds_fail() {
+        let attached = vec![Coin::new(9_000_000, "uosmo"), Coin::new(5, "uatom")];
+        assert_eq!(
+            check_creation_funds(&attached, &fixtures::params()).unwrap_err(),
+            TokenFactoryError::InsufficientFunds {
+                needed: vec![Coin::new(10_000_000, "uosmo")],
+                attached: vec![Coin::new(5, "uatom"), Coin::new(9_000_000, "uosmo")],
+            }
+        );
+    }
+
+    #[test]
+    fn excess_creation_funds_fail() {
+        let attached = vec![
+            Coin::new(10_000_001, "uosmo"),
+            Coin::new(5, "uatom"),
+            Coin::new(0, "uusdc"),
+        ];
+        assert_eq!(
+            check_creation_funds(&attached, &fixtures::params()).unwrap_err(),
+            TokenFactoryError::ExcessFunds {
+                excess: vec![Coin::new(5, "uatom"), Coin::new(1, "uosmo")],
+            }
+        );
+    }
+
+    fn regulated_launch() -> RegulatedTokenLaunch {
+        RegulatedTokenLaunch {
+            subdenom: fixtures::SUBDENOM.to_string(),
+            metadata: fixtures::metadata(),
+            hook: fixtures::hook(),
+            initial_mints: vec![InitialMint {
+                recipient: fixtures::user(),
+                amount: Uint256::from(1_000_000u128),
+            }],
+        }
+    }
+
+    #[test]
+    fn regulated_launch_sets_the_hook_before_minting() {
+        let response = regulated_launch().build(&fixtures::creator()).unwrap();
+        let msgs: Vec<CosmosMsg<TokenFactoryMsg>> = response
+            .messages
+            .into_iter()
+            .map(|submsg| submsg.msg)
+            .collect();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Custom(bare_create()),
+                CosmosMsg::Custom(fixtures::set_metadata()),
+                CosmosMsg::Custom(fixtures::set_before_send_hook()),
+                CosmosMsg::Custom(fixtures::mint_tokens()),
+            ]
+        );
+    }
+
+    #[test]
+    fn regulated_launch_rejects_an_empty_hook() {
+        let mut launch = regulated_launch();
+        launch.hook = Addr::unchecked("");
+        assert_eq!(
+            launch.build(&fixtures::creator()).unwrap_err(),
+            TokenFactoryError::EmptyHook {}.into()
+        );
+    }
+
+    #[test]
+    fn regulated_launch_requires_exchange_ready_metadata() {
+        let mut launch = regulated_launch();
+        launch.metadata.symbol = "usdx".to_string();
+        launch.build(&fixtures::creator()).unwrap_err();
+    }
+}
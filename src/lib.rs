@@ -1,8 +1,83 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CustomQuery, QuerierWrapper, StdResult, Uint256};
+use cosmwasm_std::{
+    from_json, to_json_vec, Addr, Api, Coin, ContractResult, CustomQuery, Empty, QuerierWrapper,
+    QueryRequest, StdError, StdResult, SystemError, SystemResult, Uint128, Uint256,
+};
+use serde::de::DeserializeOwned;
+
+use crate::denom::check_metadata_base;
+
+mod admin;
+mod amount;
+mod bank;
+mod batch;
+mod capability;
+mod denom;
+#[cfg(feature = "chain-envelope")]
+mod envelope;
+mod error;
+/// Ready-made values for tests of crates built on these bindings. Every message passes
+/// [`TokenFactoryMsg::validate`] and the metadata passes
+/// [`DenomMetadata::validate_exchange_ready`].
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fixtures;
+mod hook;
+mod launch;
+mod metadata;
+mod pagination;
+mod reply;
+#[cfg(feature = "schema-validate")]
+mod schema;
+#[cfg(feature = "stargate")]
+mod stargate;
+mod supply;
+#[cfg(test)]
+mod testing;
+mod transfer;
+mod validate;
+
+pub use admin::is_admin_str;
+pub use amount::IntoAmount;
+pub use batch::{
+    airdrop_submsgs, all_token_factory, batch_events, batch_into_submsg, detect_order_hazards,
+    extract_token_factory, mint_coins, required_funds_for_batch, summarize_batch,
+    total_minted_coin, BatchSummary, OpTotals, OrderHazard, TokenFactoryOp,
+};
+#[cfg(feature = "multi-admin")]
+pub use capability::CAPABILITY_MULTI_ADMIN;
+pub use capability::{
+    required_capability_set, CAPABILITY_BEFORE_SEND_HOOK, CAPABILITY_FORCE_TRANSFER,
+    CAPABILITY_TOKEN_FACTORY,
+};
+pub use denom::{
+    assert_denom_roundtrip, canonicalize_denom, denoms_equal, full_denom, parse_full_denom,
+    subdenom_fits, Subdenom, FACTORY_DENOM_PREFIX, MAX_SUBDENOM_LENGTH,
+};
+#[cfg(feature = "chain-envelope")]
+pub use envelope::{TokenFactoryEnvelope, TOKEN_FACTORY_ROUTE};
+pub use error::TokenFactoryError;
+pub use hook::{BeforeSendHookResult, BeforeSendHookSudoMsg, FreezeList};
+pub use launch::{
+    check_creation_funds, create_denom_with_hook, create_with_derived_base, InitialMint,
+    LaunchEstimate, RegulatedTokenLaunch, TokenLaunch, TokenSpec,
+};
+pub use pagination::{PageKey, PageRequest, PageResponse};
+pub use reply::{
+    parse_create_denom_reply, token_factory_create_denom_submsg, CREATE_DENOM_EVENT,
+    NEW_TOKEN_DENOM_ATTRIBUTE,
+};
+#[cfg(feature = "schema-validate")]
+pub use schema::validate_msg_json;
+#[cfg(feature = "stargate")]
+pub use stargate::{
+    mint_coin_proto, proto, StargateTokenFactoryQuerier, OSMOSIS_TOKEN_FACTORY_PACKAGE,
+};
+pub use supply::{reconcile_supply, DenomAmounts, SupplyDelta};
+pub use transfer::{validate_force_transfer_source_not_module, KNOWN_MODULE_ACCOUNTS};
 
 /// A number of Custom messages that can call into the TokenFactory bindings
 #[cw_serde]
+#[non_exhaustive]
 pub enum TokenFactoryMsg {
     /// CreateDenom creates a new factory denom, of denomination:
     /// factory/{creating contract address}/{Subdenom}
@@ -44,11 +119,124 @@ pub enum TokenFactoryMsg {
         to_address: Addr,
         amount: Uint256,
     },
+    /// Sets the contract called before every transfer of a denom the contract is admin of,
+    /// which can block the transfer. An empty contract address removes the hook.
+    SetBeforeSendHook { denom: String, contract_addr: Addr },
+    /// Replaces the admins of a denom on chains that support several admins per denom.
+    /// An empty list leaves the denom without an admin. Chains with a single admin per
+    /// denom, the default, reject this message; use ChangeAdmin there.
+    #[cfg(feature = "multi-admin")]
+    ChangeAdmins {
+        denom: String,
+        new_admins: Vec<Addr>,
+    },
+}
+
+impl TokenFactoryMsg {
+    /// Returns true if the message changes the total supply of a denom.
+    /// Only minting and burning do; a ForceTransfer moves existing tokens.
+    pub fn affects_supply(&self) -> bool {
+        match self {
+            TokenFactoryMsg::MintTokens { .. } | TokenFactoryMsg::BurnTokens { .. } => true,
+            TokenFactoryMsg::CreateDenom { .. }
+            | TokenFactoryMsg::ChangeAdmin { .. }
+            | TokenFactoryMsg::SetMetadata { .. }
+            | TokenFactoryMsg::ForceTransfer { .. }
+            | TokenFactoryMsg::SetBeforeSendHook { .. } => false,
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { .. } => false,
+        }
+    }
+
+    /// Returns the denom the message acts on. For SetMetadata this is the metadata base.
+    /// Returns None for CreateDenom, whose denom depends on the creator; see
+    /// [`TokenFactoryMsg::target_denom_with_creator`].
+    pub fn target_denom(&self) -> Option<String> {
+        match self {
+            TokenFactoryMsg::CreateDenom { .. } => None,
+            TokenFactoryMsg::ChangeAdmin { denom, .. }
+            | TokenFactoryMsg::MintTokens { denom, .. }
+            | TokenFactoryMsg::BurnTokens { denom, .. }
+            | TokenFactoryMsg::ForceTransfer { denom, .. }
+            | TokenFactoryMsg::SetBeforeSendHook { denom, .. } => Some(denom.clone()),
+            TokenFactoryMsg::SetMetadata { metadata } => Some(metadata.base.clone()),
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { denom, .. } => Some(denom.clone()),
+        }
+    }
+
+    /// Like [`TokenFactoryMsg::target_denom`], but also resolves the denom a CreateDenom
+    /// sent by `creator` creates.
+    pub fn target_denom_with_creator(&self, creator: &Addr) -> String {
+        match self {
+            TokenFactoryMsg::CreateDenom { subdenom, .. } => full_denom(creator, subdenom),
+            msg => msg
+                .target_denom()
+                .expect("only CreateDenom has no target denom"),
+        }
+    }
+
+    /// Returns the message that undoes this one, assuming `me` is still the admin: a burn of
+    /// the same denom and amount for a mint to `me`, or a mint back to the address a burn
+    /// took from. Returns None for a mint to anyone else, since the chain only burns from the
+    /// admin's own balance, and for other messages.
+    pub fn inverse(&self, me: &Addr) -> Option<TokenFactoryMsg> {
+        match self {
+            TokenFactoryMsg::MintTokens {
+                denom,
+                amount,
+                mint_to_address,
+            } => (mint_to_address == me).then(|| TokenFactoryMsg::BurnTokens {
+                denom: denom.clone(),
+                amount: *amount,
+                burn_from_address: me.clone(),
+            }),
+            TokenFactoryMsg::BurnTokens {
+                denom,
+                amount,
+                burn_from_address,
+            } => Some(TokenFactoryMsg::MintTokens {
+                denom: denom.clone(),
+                amount: *amount,
+                mint_to_address: burn_from_address.clone(),
+            }),
+            TokenFactoryMsg::CreateDenom { .. }
+            | TokenFactoryMsg::ChangeAdmin { .. }
+            | TokenFactoryMsg::SetMetadata { .. }
+            | TokenFactoryMsg::ForceTransfer { .. }
+            | TokenFactoryMsg::SetBeforeSendHook { .. } => None,
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { .. } => None,
+        }
+    }
+
+    /// Returns true if the message cannot be taken back, so a wallet should ask for extra
+    /// confirmation: a ChangeAdmin to the empty address (or a ChangeAdmins to no admins)
+    /// leaves the denom without an admin for good, and a burn destroys the holder's tokens
+    /// (minting replacements is new issuance, not an undo). A ChangeAdmin to another address
+    /// is not counted, since that address can hand the admin back. Everything else can be
+    /// undone or overwritten by the admin.
+    pub fn is_irreversible(&self) -> bool {
+        match self {
+            TokenFactoryMsg::ChangeAdmin {
+                new_admin_address, ..
+            } => new_admin_address.as_str().is_empty(),
+            TokenFactoryMsg::BurnTokens { .. } => true,
+            TokenFactoryMsg::CreateDenom { .. }
+            | TokenFactoryMsg::MintTokens { .. }
+            | TokenFactoryMsg::SetMetadata { .. }
+            | TokenFactoryMsg::ForceTransfer { .. }
+            | TokenFactoryMsg::SetBeforeSendHook { .. } => false,
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { new_admins, .. } => new_admins.is_empty(),
+        }
+    }
 }
 
 /// TokenFactory-specific queries
 #[cw_serde]
 #[derive(QueryResponses)]
+#[non_exhaustive]
 pub enum TokenFactoryQuery {
     #[returns(FullDenomResponse)]
     FullDenom {
@@ -63,6 +251,45 @@ pub enum TokenFactoryQuery {
     DenomsByCreator { creator: Addr },
     #[returns(TokenParamsResponse)]
     Params {},
+    /// Returns the block height at which the denom was created.
+    /// Only supported by chains that record it; elsewhere the query fails
+    /// and callers should treat the creation height as unknown.
+    #[returns(DenomCreationHeightResponse)]
+    DenomCreationHeight { denom: String },
+    /// Lists every address that has created a denom, for indexers and explorers.
+    /// Only supported by chains that index creators; elsewhere the query fails and
+    /// callers have to collect creators from denom creation events instead.
+    #[returns(AllCreatorsResponse)]
+    AllCreators { pagination: Option<PageRequest> },
+    /// Returns the number of denoms created by `creator`.
+    /// Only supported by some chains; see
+    /// [`TokenFactoryQuerier::query_token_factory_denom_count_by_creator`] for the fallback.
+    #[returns(DenomCountResponse)]
+    DenomCountByCreator { creator: Addr },
+    /// Returns the block height at which the denom's metadata was last set.
+    /// Only supported by chains that record it; elsewhere the query fails
+    /// and callers should treat cached metadata as possibly stale.
+    #[returns(MetadataUpdatedAtResponse)]
+    MetadataUpdatedAt { denom: String },
+    #[returns(BeforeSendHookResponse)]
+    BeforeSendHookAddress { denom: String },
+    /// Returns the metadata of the factory denom behind an IBC denom, given the hash in
+    /// `ibc/{hash}`, so UIs can show IBC-wrapped factory tokens by their original name.
+    /// Only supported by chains that track the origin of IBC denoms; elsewhere the query
+    /// fails and callers have to resolve the denom trace and query Metadata of the base denom.
+    #[returns(MetadataResponse)]
+    MetadataByIbcHash { ibc_hash: String },
+    /// Returns whether the denom was created by the token factory of this chain, which
+    /// parsing the `factory/` prefix alone cannot tell. Only supported by some chains; see
+    /// [`TokenFactoryQuerier::query_token_factory_is_factory_denom`] for the fallback.
+    #[returns(IsFactoryDenomResponse)]
+    IsFactoryDenom { denom: String },
+    /// Returns every admin of the denom.
+    /// Only supported by chains that allow several admins per denom; elsewhere the query
+    /// fails and callers should use Admin, which returns the single admin.
+    #[cfg(feature = "multi-admin")]
+    #[returns(AdminsResponse)]
+    Admins { denom: String },
 }
 
 /// DenomUnit is used to describe a token for the Bank module; part of the SetDenomMetadata message
@@ -97,6 +324,26 @@ pub struct DenomMetadata {
     pub symbol: String,
 }
 
+/// The response to any TokenFactoryQuery, for contracts that forward queries generically.
+#[cw_serde]
+#[non_exhaustive]
+pub enum TokenFactoryQueryResponse {
+    FullDenom(FullDenomResponse),
+    Admin(AdminResponse),
+    Metadata(MetadataResponse),
+    DenomsByCreator(DenomsByCreatorResponse),
+    Params(TokenParamsResponse),
+    DenomCreationHeight(DenomCreationHeightResponse),
+    AllCreators(AllCreatorsResponse),
+    DenomCountByCreator(DenomCountResponse),
+    MetadataUpdatedAt(MetadataUpdatedAtResponse),
+    BeforeSendHookAddress(BeforeSendHookResponse),
+    MetadataByIbcHash(MetadataResponse),
+    IsFactoryDenom(IsFactoryDenomResponse),
+    #[cfg(feature = "multi-admin")]
+    Admins(AdminsResponse),
+}
+
 #[cw_serde]
 pub struct FullDenomResponse {
     pub denom: String,
@@ -107,16 +354,55 @@ pub struct AdminResponse {
     pub admin: String,
 }
 
+#[cfg(feature = "multi-admin")]
+#[cw_serde]
+pub struct AdminsResponse {
+    /// The admins of the denom, empty if it has none.
+    pub admins: Vec<String>,
+}
+
 #[cw_serde]
 pub struct MetadataResponse {
     pub metadata: Option<DenomMetadata>,
 }
 
+#[cw_serde]
+pub struct MetadataUpdatedAtResponse {
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct BeforeSendHookResponse {
+    /// The hook contract, or None if the denom has no before-send hook.
+    pub contract_addr: Option<String>,
+}
+
+#[cw_serde]
+pub struct IsFactoryDenomResponse {
+    pub is_factory: bool,
+}
+
 #[cw_serde]
 pub struct DenomsByCreatorResponse {
     pub denoms: Vec<String>,
 }
 
+#[cw_serde]
+pub struct DenomCountResponse {
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct DenomCreationHeightResponse {
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct AllCreatorsResponse {
+    pub creators: Vec<String>,
+    pub pagination: Option<PageResponse>,
+}
+
 #[cw_serde]
 pub struct TokenParamsResponse {
     pub params: TokenParams,
@@ -125,6 +411,42 @@ pub struct TokenParamsResponse {
 #[cw_serde]
 pub struct TokenParams {
     pub denom_creation_fee: Vec<DenomCreationFee>,
+    /// Gas consumed by denom creation on top of the message's regular gas,
+    /// on chains that charge gas instead of or in addition to a fee.
+    #[serde(default)]
+    pub denom_creation_gas_consume: Option<u64>,
+    /// Maximum subdenom length, on chains that make it configurable.
+    /// None means the default of [`MAX_SUBDENOM_LENGTH`].
+    #[serde(default)]
+    pub max_subdenom_length: Option<u32>,
+}
+
+impl TokenParams {
+    /// Returns the extra gas a CreateDenom consumes, or 0 if the chain does not report it.
+    /// Wallets add this to the simulated gas of the rest of the transaction.
+    pub fn total_create_gas(&self) -> u64 {
+        self.denom_creation_gas_consume.unwrap_or_default()
+    }
+
+    /// Returns the funds one CreateDenom costs, one coin per fee denom, ordered by denom.
+    /// Zero fees are left out, since the bank module rejects zero coins in funds.
+    /// Errors if a fee overflows or does not fit a `Uint128`.
+    pub fn creation_fee_coins(&self) -> StdResult<Vec<Coin>> {
+        let mut fees = DenomAmounts::default();
+        for fee in &self.denom_creation_fee {
+            fees.add(&fee.denom, fee.amount)?;
+        }
+        fees.0
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(denom, amount)| {
+                Ok(Coin {
+                    amount: Uint128::try_from(amount)?,
+                    denom,
+                })
+            })
+            .collect()
+    }
 }
 
 #[cw_serde]
@@ -140,6 +462,29 @@ pub trait CreateTokenFactoryMsg: From<TokenFactoryMsg> {
     ) -> StdResult<Self> {
         Ok(TokenFactoryMsg::CreateDenom { subdenom, metadata }.into())
     }
+    /// Creates a denom without metadata.
+    fn token_factory_create_denom_bare(subdenom: String) -> StdResult<Self> {
+        Self::token_factory_create_denom(subdenom, None)
+    }
+    /// Creates a denom and sets its metadata in the same message.
+    fn token_factory_create_denom_with_metadata(
+        subdenom: String,
+        metadata: DenomMetadata,
+    ) -> StdResult<Self> {
+        Self::token_factory_create_denom(subdenom, Some(metadata))
+    }
+    /// Same as token_factory_create_denom, but errors if the metadata base is not
+    /// the full denom that `creator` will get for `subdenom`, which strict chains reject.
+    fn token_factory_create_denom_checked(
+        creator: &Addr,
+        subdenom: String,
+        metadata: Option<DenomMetadata>,
+    ) -> StdResult<Self> {
+        if let Some(metadata) = &metadata {
+            check_metadata_base(creator, &subdenom, metadata)?;
+        }
+        Self::token_factory_create_denom(subdenom, metadata)
+    }
     fn token_factory_change_admin(denom: String, new_admin_address: Addr) -> StdResult<Self> {
         Ok(TokenFactoryMsg::ChangeAdmin {
             denom,
@@ -149,24 +494,41 @@ pub trait CreateTokenFactoryMsg: From<TokenFactoryMsg> {
     }
     fn token_factory_mint_tokens(
         denom: String,
-        amount: Uint256,
+        amount: impl IntoAmount,
         mint_to_address: Addr,
     ) -> StdResult<Self> {
         Ok(TokenFactoryMsg::MintTokens {
             denom,
-            amount,
+            amount: amount.into_amount(),
             mint_to_address,
         }
         .into())
     }
+    /// Mints to a user, erroring with MintToSelf if `user` is the contract itself, which is
+    /// usually a mix-up of the recipient. Minting to the contract is sometimes intended,
+    /// e.g. to hold a treasury; use token_factory_mint_tokens for that.
+    fn token_factory_mint_to_user(
+        denom: String,
+        amount: impl IntoAmount,
+        user: &Addr,
+        me: &Addr,
+    ) -> StdResult<Self> {
+        if user == me {
+            return Err(TokenFactoryError::MintToSelf {
+                address: me.to_string(),
+            }
+            .into());
+        }
+        Self::token_factory_mint_tokens(denom, amount, user.clone())
+    }
     fn token_factory_burn_tokens(
         denom: String,
-        amount: Uint256,
+        amount: impl IntoAmount,
         burn_from_address: Addr,
     ) -> StdResult<Self> {
         Ok(TokenFactoryMsg::BurnTokens {
             denom,
-            amount,
+            amount: amount.into_amount(),
             burn_from_address,
         }
         .into())
@@ -178,20 +540,34 @@ pub trait CreateTokenFactoryMsg: From<TokenFactoryMsg> {
         denom: String,
         from_address: Addr,
         to_address: Addr,
-        amount: Uint256,
+        amount: impl IntoAmount,
     ) -> StdResult<Self> {
         Ok(TokenFactoryMsg::ForceTransfer {
             denom,
             from_address,
             to_address,
-            amount,
+            amount: amount.into_amount(),
+        }
+        .into())
+    }
+    fn token_factory_set_before_send_hook(denom: String, contract_addr: Addr) -> StdResult<Self> {
+        Ok(TokenFactoryMsg::SetBeforeSendHook {
+            denom,
+            contract_addr,
         }
         .into())
     }
+    #[cfg(feature = "multi-admin")]
+    fn token_factory_change_admins(denom: String, new_admins: Vec<Addr>) -> StdResult<Self> {
+        Ok(TokenFactoryMsg::ChangeAdmins { denom, new_admins }.into())
+    }
 }
 
 impl<T> CreateTokenFactoryMsg for T where T: From<TokenFactoryMsg> {}
 
+/// Token factory queries. Only the queries every token factory chain serves are required; the
+/// others default to an error, or to a fallback built on the required ones where documented,
+/// and are overridden by implementations that support them.
 pub trait TokenFactoryQuerier {
     fn query_token_factory_full_denom(
         &self,
@@ -209,6 +585,311 @@ pub trait TokenFactoryQuerier {
     ) -> StdResult<DenomsByCreatorResponse>;
 
     fn query_token_factory_params(&self) -> StdResult<TokenParamsResponse>;
+
+    fn query_token_factory_denom_creation_height(
+        &self,
+        _denom: String,
+    ) -> StdResult<DenomCreationHeightResponse> {
+        Err(StdError::generic_err(
+            "DenomCreationHeight is not supported by this querier",
+        ))
+    }
+
+    fn query_token_factory_all_creators(
+        &self,
+        _pagination: Option<PageRequest>,
+    ) -> StdResult<AllCreatorsResponse> {
+        Err(StdError::generic_err(
+            "AllCreators is not supported by this querier",
+        ))
+    }
+
+    /// Counts the denoms of `creator` with the DenomCountByCreator query, falling back
+    /// to counting the DenomsByCreator result on chains that answer it with an
+    /// unsupported-request error. Other errors are returned.
+    /// By default it only counts the DenomsByCreator result.
+    fn query_token_factory_denom_count_by_creator(
+        &self,
+        creator: Addr,
+    ) -> StdResult<DenomCountResponse> {
+        let denoms = self.query_token_factory_denoms_by_creator(creator)?.denoms;
+        Ok(DenomCountResponse {
+            count: denoms.len() as u64,
+        })
+    }
+
+    fn query_token_factory_metadata_updated_at(
+        &self,
+        _denom: String,
+    ) -> StdResult<MetadataUpdatedAtResponse> {
+        Err(StdError::generic_err(
+            "MetadataUpdatedAt is not supported by this querier",
+        ))
+    }
+
+    fn query_token_factory_before_send_hook(
+        &self,
+        _denom: String,
+    ) -> StdResult<BeforeSendHookResponse> {
+        Err(StdError::generic_err(
+            "BeforeSendHookAddress is not supported by this querier",
+        ))
+    }
+
+    fn query_token_factory_metadata_by_ibc_hash(
+        &self,
+        _ibc_hash: String,
+    ) -> StdResult<MetadataResponse> {
+        Err(StdError::generic_err(
+            "MetadataByIbcHash is not supported by this querier",
+        ))
+    }
+
+    /// Asks the chain whether `denom` is a factory denom with the IsFactoryDenom query,
+    /// falling back to checking that it parses as factory/{creator}/{subdenom} on chains
+    /// that answer it with an unsupported-request error. Other errors are returned. The
+    /// fallback also accepts denoms that only look like factory denoms, e.g. ones never
+    /// created. By default it only does the parse check.
+    fn query_token_factory_is_factory_denom(
+        &self,
+        denom: String,
+    ) -> StdResult<IsFactoryDenomResponse> {
+        Ok(IsFactoryDenomResponse {
+            is_factory: parse_full_denom(&denom).is_some(),
+        })
+    }
+
+    #[cfg(feature = "multi-admin")]
+    fn query_token_factory_admins(&self, _denom: String) -> StdResult<AdminsResponse> {
+        Err(StdError::generic_err(
+            "Admins is not supported by this querier",
+        ))
+    }
+
+    /// Runs any query and wraps its response, for contracts that proxy token factory queries.
+    fn query_any(&self, query: TokenFactoryQuery) -> StdResult<TokenFactoryQueryResponse> {
+        Ok(match query {
+            TokenFactoryQuery::FullDenom {
+                subdenom,
+                creator_addr,
+            } => TokenFactoryQueryResponse::FullDenom(
+                self.query_token_factory_full_denom(subdenom, creator_addr)?,
+            ),
+            TokenFactoryQuery::Admin { denom } => {
+                TokenFactoryQueryResponse::Admin(self.query_token_factory_admin(denom)?)
+            }
+            TokenFactoryQuery::Metadata { denom } => {
+                TokenFactoryQueryResponse::Metadata(self.query_token_factory_metadata(denom)?)
+            }
+            TokenFactoryQuery::DenomsByCreator { creator } => {
+                TokenFactoryQueryResponse::DenomsByCreator(
+                    self.query_token_factory_denoms_by_creator(creator)?,
+                )
+            }
+            TokenFactoryQuery::Params {} => {
+                TokenFactoryQueryResponse::Params(self.query_token_factory_params()?)
+            }
+            TokenFactoryQuery::DenomCreationHeight { denom } => {
+                TokenFactoryQueryResponse::DenomCreationHeight(
+                    self.query_token_factory_denom_creation_height(denom)?,
+                )
+            }
+            TokenFactoryQuery::AllCreators { pagination } => {
+                TokenFactoryQueryResponse::AllCreators(
+                    self.query_token_factory_all_creators(pagination)?,
+                )
+            }
+            TokenFactoryQuery::DenomCountByCreator { creator } => {
+                TokenFactoryQueryResponse::DenomCountByCreator(
+                    self.query_token_factory_denom_count_by_creator(creator)?,
+                )
+            }
+            TokenFactoryQuery::MetadataUpdatedAt { denom } => {
+                TokenFactoryQueryResponse::MetadataUpdatedAt(
+                    self.query_token_factory_metadata_updated_at(denom)?,
+                )
+            }
+            TokenFactoryQuery::BeforeSendHookAddress { denom } => {
+                TokenFactoryQueryResponse::BeforeSendHookAddress(
+                    self.query_token_factory_before_send_hook(denom)?,
+                )
+            }
+            TokenFactoryQuery::MetadataByIbcHash { ibc_hash } => {
+                TokenFactoryQueryResponse::MetadataByIbcHash(
+                    self.query_token_factory_metadata_by_ibc_hash(ibc_hash)?,
+                )
+            }
+            TokenFactoryQuery::IsFactoryDenom { denom } => {
+                TokenFactoryQueryResponse::IsFactoryDenom(
+                    self.query_token_factory_is_factory_denom(denom)?,
+                )
+            }
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryQuery::Admins { denom } => {
+                TokenFactoryQueryResponse::Admins(self.query_token_factory_admins(denom)?)
+            }
+        })
+    }
+
+    /// Queries the before-send hook of each denom, in the order given.
+    fn before_send_hooks_many(
+        &self,
+        denoms: Vec<String>,
+    ) -> StdResult<Vec<BeforeSendHookResponse>> {
+        denoms
+            .into_iter()
+            .map(|denom| self.query_token_factory_before_send_hook(denom))
+            .collect()
+    }
+
+    /// Errors with [`TokenFactoryError::DenomNotFound`] unless `creator` has created
+    /// `subdenom`, to fail a mint or burn with a clearer error than the chain's.
+    fn ensure_denom_exists(&self, creator: Addr, subdenom: String) -> StdResult<()> {
+        let denom = full_denom(&creator, &subdenom);
+        let denoms = self.query_token_factory_denoms_by_creator(creator)?.denoms;
+        if !denoms.contains(&denom) {
+            return Err(TokenFactoryError::DenomNotFound { denom }.into());
+        }
+        Ok(())
+    }
+
+    /// Returns true if `creator` can still create `subdenom`, i.e. it is a valid subdenom
+    /// and `creator` has not created it yet. Errors if the subdenom is invalid.
+    fn subdenom_available(&self, creator: Addr, subdenom: String) -> StdResult<bool> {
+        let subdenom = Subdenom::new(subdenom)?;
+        let denom = full_denom(&creator, subdenom.as_str());
+        let denoms = self.query_token_factory_denoms_by_creator(creator)?.denoms;
+        Ok(!denoms.contains(&denom))
+    }
+
+    /// Errors with [`TokenFactoryError::DecimalsMismatch`] unless the metadata of `denom`
+    /// exists and its display unit is listed with `expected` decimals.
+    fn assert_decimals(&self, denom: String, expected: u32) -> StdResult<()> {
+        let actual = self
+            .query_token_factory_metadata(denom)?
+            .metadata
+            .and_then(|metadata| metadata.display_exponent());
+        if actual != Some(expected) {
+            return Err(TokenFactoryError::DecimalsMismatch { expected, actual }.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the metadata of `denom`, or if it has none, the metadata of `template_denom`
+    /// moved to `denom`: the template's base unit becomes `denom` (without its aliases), and
+    /// so does the display if it was the base. Name, symbol, description and the other units
+    /// are kept, e.g. for LP tokens displayed like their underlying token.
+    /// Errors with [`TokenFactoryError::NoMetadataToInherit`] if neither denom has metadata.
+    fn metadata_or_inherit(
+        &self,
+        denom: String,
+        template_denom: String,
+    ) -> StdResult<DenomMetadata> {
+        if let Some(metadata) = self.query_token_factory_metadata(denom.clone())?.metadata {
+            return Ok(metadata);
+        }
+        let mut metadata = self
+            .query_token_factory_metadata(template_denom.clone())?
+            .metadata
+            .ok_or_else(|| TokenFactoryError::NoMetadataToInherit {
+                denom: denom.clone(),
+                template: template_denom,
+            })?;
+        for unit in &mut metadata.denom_units {
+            if unit.denom == metadata.base {
+                unit.denom = denom.clone();
+                unit.aliases.clear();
+            }
+        }
+        if metadata.display == metadata.base {
+            metadata.display = denom.clone();
+        }
+        metadata.base = denom;
+        Ok(metadata)
+    }
+
+    /// Formats an amount of `denom` for display, e.g. "1.5 ATOM".
+    /// Falls back to the raw amount and denom, e.g. "1500000 uatom", if the denom has no metadata.
+    fn format_amount(&self, denom: String, amount: Uint256) -> StdResult<String> {
+        let metadata = self.query_token_factory_metadata(denom.clone())?.metadata;
+        Ok(match metadata {
+            Some(metadata) => metadata.format_amount(amount),
+            None => format!("{} {}", amount, denom),
+        })
+    }
+
+    /// Returns the validated admin of `denom`, or None if it has no admin.
+    fn admin_addr(&self, api: &dyn Api, denom: String) -> StdResult<Option<Addr>> {
+        let admin = self.query_token_factory_admin(denom)?.admin;
+        if admin.is_empty() {
+            return Ok(None);
+        }
+        api.addr_validate(&admin).map(Some)
+    }
+
+    /// Returns true if creating a denom costs a nonzero fee on this chain.
+    fn creation_requires_funds(&self) -> StdResult<bool> {
+        let params = self.query_token_factory_params()?.params;
+        Ok(params
+            .denom_creation_fee
+            .iter()
+            .any(|fee| !fee.amount.is_zero()))
+    }
+
+    /// Returns the denoms of `creator` whose subdenom starts with `subdenom_prefix`.
+    fn denoms_by_creator_prefixed(
+        &self,
+        creator: Addr,
+        subdenom_prefix: &str,
+    ) -> StdResult<Vec<String>> {
+        let denoms = self.query_token_factory_denoms_by_creator(creator)?.denoms;
+        Ok(denoms
+            .into_iter()
+            .filter(|denom| {
+                parse_full_denom(denom)
+                    .map(|(_, subdenom)| subdenom.starts_with(subdenom_prefix))
+                    .unwrap_or(false)
+            })
+            .collect())
+    }
+
+    /// Returns a ChangeAdmin that removes the admin of `denom`, or None if `me` is not
+    /// the current admin and the revoke would fail.
+    fn revoke_admin_if_admin(
+        &self,
+        denom: String,
+        me: &Addr,
+    ) -> StdResult<Option<TokenFactoryMsg>> {
+        let admin = self.query_token_factory_admin(denom.clone())?.admin;
+        if admin != me.as_str() {
+            return Ok(None);
+        }
+        Ok(Some(TokenFactoryMsg::ChangeAdmin {
+            denom,
+            new_admin_address: Addr::unchecked(""),
+        }))
+    }
+}
+
+/// Runs a custom query like [`QuerierWrapper::query`], but returns None if the chain answers
+/// with an unsupported-request error, as chains do for custom queries they do not know.
+fn query_if_supported<C: CustomQuery, U: DeserializeOwned>(
+    querier: &QuerierWrapper<C>,
+    query: C,
+) -> StdResult<Option<U>> {
+    let request = to_json_vec(&QueryRequest::Custom(query))?;
+    match querier.raw_query(&request) {
+        SystemResult::Err(SystemError::UnsupportedRequest { .. }) => Ok(None),
+        SystemResult::Err(err) => Err(StdError::generic_err(format!(
+            "Querier system error: {}",
+            err
+        ))),
+        SystemResult::Ok(ContractResult::Err(err)) => Err(StdError::generic_err(format!(
+            "Querier contract error: {}",
+            err
+        ))),
+        SystemResult::Ok(ContractResult::Ok(data)) => from_json(data).map(Some),
+    }
 }
 
 impl<'a, T> TokenFactoryQuerier for QuerierWrapper<'a, T>
@@ -250,9 +931,983 @@ where
         let custom_query: T = TokenFactoryQuery::Params {}.into();
         self.query(&custom_query.into())
     }
+
+    fn query_token_factory_denom_creation_height(
+        &self,
+        denom: String,
+    ) -> StdResult<DenomCreationHeightResponse> {
+        let custom_query: T = TokenFactoryQuery::DenomCreationHeight { denom }.into();
+        self.query(&custom_query.into())
+    }
+
+    fn query_token_factory_all_creators(
+        &self,
+        pagination: Option<PageRequest>,
+    ) -> StdResult<AllCreatorsResponse> {
+        let custom_query: T = TokenFactoryQuery::AllCreators { pagination }.into();
+        self.query(&custom_query.into())
+    }
+
+    fn query_token_factory_denom_count_by_creator(
+        &self,
+        creator: Addr,
+    ) -> StdResult<DenomCountResponse> {
+        let custom_query: T = TokenFactoryQuery::DenomCountByCreator {
+            creator: creator.clone(),
+        }
+        .into();
+        if let Some(response) = query_if_supported(self, custom_query)? {
+            return Ok(response);
+        }
+        let denoms = self.query_token_factory_denoms_by_creator(creator)?.denoms;
+        Ok(DenomCountResponse {
+            count: denoms.len() as u64,
+        })
+    }
+
+    fn query_token_factory_metadata_updated_at(
+        &self,
+        denom: String,
+    ) -> StdResult<MetadataUpdatedAtResponse> {
+        let custom_query: T = TokenFactoryQuery::MetadataUpdatedAt { denom }.into();
+        self.query(&custom_query.into())
+    }
+
+    fn query_token_factory_before_send_hook(
+        &self,
+        denom: String,
+    ) -> StdResult<BeforeSendHookResponse> {
+        let custom_query: T = TokenFactoryQuery::BeforeSendHookAddress { denom }.into();
+        self.query(&custom_query.into())
+    }
+
+    fn query_token_factory_metadata_by_ibc_hash(
+        &self,
+        ibc_hash: String,
+    ) -> StdResult<MetadataResponse> {
+        let custom_query: T = TokenFactoryQuery::MetadataByIbcHash { ibc_hash }.into();
+        self.query(&custom_query.into())
+    }
+
+    fn query_token_factory_is_factory_denom(
+        &self,
+        denom: String,
+    ) -> StdResult<IsFactoryDenomResponse> {
+        let custom_query: T = TokenFactoryQuery::IsFactoryDenom {
+            denom: denom.clone(),
+        }
+        .into();
+        if let Some(response) = query_if_supported(self, custom_query)? {
+            return Ok(response);
+        }
+        Ok(IsFactoryDenomResponse {
+            is_factory: parse_full_denom(&denom).is_some(),
+        })
+    }
+
+    #[cfg(feature = "multi-admin")]
+    fn query_token_factory_admins(&self, denom: String) -> StdResult<AdminsResponse> {
+        let custom_query: T = TokenFactoryQuery::Admins { denom }.into();
+        self.query(&custom_query.into())
+    }
+}
+
+/// Token factory helpers that also read bank balances or supply. They are kept out of
+/// [`TokenFactoryQuerier`] so that implementing it does not require bank access.
+pub trait TokenFactoryBankQuerier: TokenFactoryQuerier {
+    /// The querier the bank queries go through.
+    fn bank_querier(&self) -> QuerierWrapper<'_, Empty>;
+
+    /// Returns a BurnTokens burning `amount` of `denom` from `me`, or None if the bank
+    /// balance of `me` is too low for the burn to succeed.
+    fn burn_if_funded(
+        &self,
+        denom: String,
+        amount: Uint256,
+        me: &Addr,
+    ) -> StdResult<Option<TokenFactoryMsg>> {
+        bank::burn_if_funded(&self.bank_querier(), denom, amount, me)
+    }
+
+    /// Returns how much of each fee denom `me` still needs to pay the denom creation fee.
+    /// An empty result means the balance of `me` covers the fee.
+    fn creation_fee_shortfall(&self, me: &Addr) -> StdResult<Vec<Coin>> {
+        let fees = self.query_token_factory_params()?.params.denom_creation_fee;
+        bank::creation_fee_shortfall(&self.bank_querier(), fees, me)
+    }
+
+    /// Returns true if the current bank supply of `denom` plus the amounts of `denom`
+    /// minted in `msgs` stays within `cap`.
+    #[cfg(feature = "cosmwasm_1_1")]
+    fn mint_batch_within_cap(
+        &self,
+        denom: String,
+        msgs: &[TokenFactoryMsg],
+        cap: Uint256,
+    ) -> StdResult<bool> {
+        bank::mint_batch_within_cap(&self.bank_querier(), denom, msgs, cap)
+    }
+}
+
+impl<'a, T> TokenFactoryBankQuerier for QuerierWrapper<'a, T>
+where
+    T: CustomQuery + From<TokenFactoryQuery>,
+{
+    fn bank_querier(&self) -> QuerierWrapper<'_, Empty> {
+        QuerierWrapper::new(&**self)
+    }
 }
 
 // This export is added to all contracts that import this package, signifying that they require
 // "token_factory" support on the chain they run on.
 #[no_mangle]
 extern "C" fn requires_token_factory() {}
+
+/// Version of the token factory feature set these bindings can emit, a finer signal than the
+/// `requires_token_factory` export, which only says that some token factory is needed.
+///
+/// - 1: CreateDenom, ChangeAdmin, MintTokens, BurnTokens and SetMetadata.
+/// - 2: additionally ForceTransfer and SetBeforeSendHook.
+///
+/// It is not exported: a `requires_*` export carries no data, and chains reject uploads of
+/// contracts requiring a capability they do not know, so a versioned export would break
+/// uploads everywhere until chains add it. Contracts report the version instead, e.g. in an
+/// instantiate attribute or a query, and deployment tooling compares it with the version the
+/// target chain supports before uploading. For a finer check, see
+/// [`TokenFactoryMsg::required_capabilities`].
+pub const TOKEN_FACTORY_CAPABILITY_VERSION: u32 = 2;
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{MockApi, MockQuerier};
+    use cosmwasm_std::{from_json, Binary, QuerierWrapper};
+
+    use super::*;
+    use crate::fixtures;
+    use crate::testing::{mock_querier, respond, unsupported, RequiredOnlyQuerier};
+
+    #[test]
+    fn only_mints_and_burns_affect_supply() {
+        assert!(fixtures::mint_tokens().affects_supply());
+        assert!(fixtures::burn_tokens().affects_supply());
+
+        assert!(!fixtures::create_denom().affects_supply());
+        assert!(!fixtures::change_admin().affects_supply());
+        assert!(!fixtures::set_metadata().affects_supply());
+        assert!(!fixtures::force_transfer().affects_supply());
+        assert!(!fixtures::set_before_send_hook().affects_supply());
+        #[cfg(feature = "multi-admin")]
+        assert!(!fixtures::change_admins().affects_supply());
+    }
+
+    #[test]
+    fn format_amount_uses_the_display_unit() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::Metadata { .. } => respond(&MetadataResponse {
+                metadata: Some(fixtures::metadata()),
+            }),
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let formatted = querier
+            .format_amount(fixtures::denom(), Uint256::from(1_500_000u128))
+            .unwrap();
+        assert_eq!(formatted, "1.5 USDX");
+    }
+
+    #[test]
+    fn format_amount_falls_back_to_the_raw_amount_without_metadata() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::Metadata { .. } => respond(&MetadataResponse { metadata: None }),
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let formatted = querier
+            .format_amount("uatom".to_string(), Uint256::from(1_500_000u128))
+            .unwrap();
+        assert_eq!(formatted, "1500000 uatom");
+    }
+
+    fn admin_querier(admin: &str) -> MockQuerier<TokenFactoryQuery> {
+        let admin = admin.to_string();
+        mock_querier(&[], move |query| match query {
+            TokenFactoryQuery::Admin { .. } => respond(&AdminResponse {
+                admin: admin.clone(),
+            }),
+            _ => unsupported(),
+        })
+    }
+
+    #[test]
+    fn revoke_admin_if_admin_revokes_when_admin() {
+        let querier = admin_querier(fixtures::creator().as_str());
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let msg = querier
+            .revoke_admin_if_admin(fixtures::denom(), &fixtures::creator())
+            .unwrap();
+        assert_eq!(
+            msg,
+            Some(TokenFactoryMsg::ChangeAdmin {
+                denom: fixtures::denom(),
+                new_admin_address: Addr::unchecked(""),
+            })
+        );
+    }
+
+    #[test]
+    fn revoke_admin_if_admin_skips_when_not_admin() {
+        let querier = admin_querier("osmo1someoneelse");
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let msg = querier
+            .revoke_admin_if_admin(fixtures::denom(), &fixtures::creator())
+            .unwrap();
+        assert_eq!(msg, None);
+    }
+
+    fn denoms_querier(denoms: Vec<String>) -> MockQuerier<TokenFactoryQuery> {
+        mock_querier(&[], move |query| match query {
+            TokenFactoryQuery::DenomsByCreator { .. } => respond(&DenomsByCreatorResponse {
+                denoms: denoms.clone(),
+            }),
+            _ => unsupported(),
+        })
+    }
+
+    #[test]
+    fn denoms_by_creator_prefixed_filters_on_the_subdenom() {
+        let creator = fixtures::creator();
+        let querier = denoms_querier(vec![
+            full_denom(&creator, "lp-atom-osmo"),
+            full_denom(&creator, "uusdx"),
+            full_denom(&creator, "lp-usdx-osmo"),
+            full_denom(&creator, "xlp-atom"),
+        ]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let denoms = querier
+            .denoms_by_creator_prefixed(creator.clone(), "lp-")
+            .unwrap();
+        assert_eq!(
+            denoms,
+            vec![
+                full_denom(&creator, "lp-atom-osmo"),
+                full_denom(&creator, "lp-usdx-osmo"),
+            ]
+        );
+    }
+
+    #[test]
+    fn denom_creation_height_round_trips_through_json() {
+        let query: TokenFactoryQuery =
+            from_json(r#"{"denom_creation_height":{"denom":"factory/creator/uusdx"}}"#).unwrap();
+        assert_eq!(
+            query,
+            TokenFactoryQuery::DenomCreationHeight {
+                denom: "factory/creator/uusdx".to_string(),
+            }
+        );
+        let response: DenomCreationHeightResponse = from_json(r#"{"height":1234}"#).unwrap();
+        assert_eq!(response, DenomCreationHeightResponse { height: 1234 });
+    }
+
+    #[test]
+    fn denom_creation_height_queries_the_chain() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::DenomCreationHeight { .. } => {
+                respond(&DenomCreationHeightResponse { height: 1234 })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_denom_creation_height(fixtures::denom())
+            .unwrap();
+        assert_eq!(response.height, 1234);
+    }
+
+    #[test]
+    fn inverse_of_a_mint_to_the_admin_is_a_burn() {
+        let me = fixtures::creator();
+        let mint = TokenFactoryMsg::MintTokens {
+            denom: fixtures::denom(),
+            amount: Uint256::from(100u128),
+            mint_to_address: me.clone(),
+        };
+        assert_eq!(
+            mint.inverse(&me),
+            Some(TokenFactoryMsg::BurnTokens {
+                denom: fixtures::denom(),
+                amount: Uint256::from(100u128),
+                burn_from_address: me,
+            })
+        );
+    }
+
+    #[test]
+    fn inverse_of_a_burn_mints_back() {
+        let burn = fixtures::burn_tokens();
+        let inverse = burn.inverse(&fixtures::creator()).unwrap();
+        assert_eq!(
+            inverse,
+            TokenFactoryMsg::MintTokens {
+                denom: fixtures::denom(),
+                amount: Uint256::from(500_000u128),
+                mint_to_address: fixtures::creator(),
+            }
+        );
+    }
+
+    #[test]
+    fn inverse_is_none_for_mints_to_others_and_other_messages() {
+        let me = fixtures::creator();
+        assert_eq!(fixtures::mint_tokens().inverse(&me), None);
+        assert_eq!(fixtures::change_admin().inverse(&me), None);
+        assert_eq!(fixtures::force_transfer().inverse(&me), None);
+    }
+
+    #[test]
+    fn all_creators_pages_through_the_chain() {
+        let creators = ["osmo1a", "osmo1b", "osmo1c"];
+        let querier = mock_querier(&[], move |query| match query {
+            TokenFactoryQuery::AllCreators { pagination } => {
+                let pagination = pagination.clone().unwrap_or_default();
+                let start = pagination.key.map_or(0, |key| key[0] as usize);
+                let end = (start + pagination.limit.unwrap_or(100) as usize).min(creators.len());
+                respond(&AllCreatorsResponse {
+                    creators: creators[start..end].iter().map(|c| c.to_string()).collect(),
+                    pagination: Some(PageResponse {
+                        next_key: (end < creators.len()).then(|| Binary::from(vec![end as u8])),
+                        total: None,
+                    }),
+                })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+
+        let first = querier
+            .query_token_factory_all_creators(Some(PageRequest::with_limit(2)))
+            .unwrap();
+        assert_eq!(first.creators, vec!["osmo1a", "osmo1b"]);
+        let next_key = first.pagination.unwrap().next_page_key().unwrap();
+
+        let second = querier
+            .query_token_factory_all_creators(Some(PageRequest::after(next_key, 2)))
+            .unwrap();
+        assert_eq!(second.creators, vec!["osmo1c"]);
+        assert_eq!(second.pagination.unwrap().next_page_key(), None);
+    }
+
+    #[test]
+    fn denom_count_by_creator_uses_the_query_when_supported() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::DenomCountByCreator { .. } => {
+                respond(&DenomCountResponse { count: 7 })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_denom_count_by_creator(fixtures::creator())
+            .unwrap();
+        assert_eq!(response.count, 7);
+    }
+
+    #[test]
+    fn denom_count_by_creator_falls_back_to_counting_denoms() {
+        let creator = fixtures::creator();
+        let querier = denoms_querier(vec![full_denom(&creator, "a"), full_denom(&creator, "b")]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_denom_count_by_creator(creator)
+            .unwrap();
+        assert_eq!(response.count, 2);
+    }
+
+    #[test]
+    fn denom_count_by_creator_returns_errors_other_than_unsupported() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::DenomCountByCreator { .. } => {
+                SystemResult::Ok(ContractResult::Err("out of gas".to_string()))
+            }
+            _ => respond(&DenomsByCreatorResponse { denoms: vec![] }),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let err = querier
+            .query_token_factory_denom_count_by_creator(fixtures::creator())
+            .unwrap_err();
+        assert!(err.to_string().contains("out of gas"));
+    }
+
+    #[test]
+    fn denom_count_by_creator_defaults_to_counting_denoms() {
+        let querier = RequiredOnlyQuerier {
+            denoms: vec![fixtures::denom()],
+        };
+        let response = querier
+            .query_token_factory_denom_count_by_creator(fixtures::creator())
+            .unwrap();
+        assert_eq!(response.count, 1);
+    }
+
+    #[test]
+    fn create_denom_bare_has_no_metadata() {
+        let msg = TokenFactoryMsg::token_factory_create_denom_bare(fixtures::SUBDENOM.to_string())
+            .unwrap();
+        assert_eq!(
+            msg,
+            TokenFactoryMsg::CreateDenom {
+                subdenom: fixtures::SUBDENOM.to_string(),
+                metadata: None,
+            }
+        );
+    }
+
+    #[test]
+    fn create_denom_with_metadata_sets_it() {
+        let msg = TokenFactoryMsg::token_factory_create_denom_with_metadata(
+            fixtures::SUBDENOM.to_string(),
+            fixtures::metadata(),
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            TokenFactoryMsg::CreateDenom {
+                subdenom: fixtures::SUBDENOM.to_string(),
+                metadata: Some(fixtures::metadata()),
+            }
+        );
+    }
+
+    fn params_querier(params: TokenParams) -> MockQuerier<TokenFactoryQuery> {
+        mock_querier(&[], move |query| match query {
+            TokenFactoryQuery::Params {} => respond(&TokenParamsResponse {
+                params: params.clone(),
+            }),
+            _ => unsupported(),
+        })
+    }
+
+    #[test]
+    fn creation_requires_funds_on_a_paid_chain() {
+        let querier = params_querier(fixtures::params());
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        assert!(querier.creation_requires_funds().unwrap());
+    }
+
+    #[test]
+    fn creation_is_free_without_fees_or_with_zero_fees() {
+        for denom_creation_fee in [
+            vec![],
+            vec![DenomCreationFee {
+                amount: Uint256::zero(),
+                denom: "uosmo".to_string(),
+            }],
+        ] {
+            let querier = params_querier(TokenParams {
+                denom_creation_fee,
+                ..fixtures::params()
+            });
+            let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+            assert!(!querier.creation_requires_funds().unwrap());
+        }
+    }
+
+    #[test]
+    fn admin_addr_validates_the_admin() {
+        let querier = admin_querier(fixtures::new_admin().as_str());
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let admin = querier
+            .admin_addr(&MockApi::default(), fixtures::denom())
+            .unwrap();
+        assert_eq!(admin, Some(fixtures::new_admin()));
+    }
+
+    #[test]
+    fn admin_addr_is_none_without_admin() {
+        let querier = admin_querier("");
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let admin = querier
+            .admin_addr(&MockApi::default(), fixtures::denom())
+            .unwrap();
+        assert_eq!(admin, None);
+    }
+
+    #[test]
+    fn admin_addr_rejects_an_invalid_admin() {
+        let querier = admin_querier(&fixtures::new_admin().as_str().to_uppercase());
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        querier
+            .admin_addr(&MockApi::default(), fixtures::denom())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn params_without_gas_consume_parse_with_none() {
+        let params: TokenParams =
+            from_json(br#"{"denom_creation_fee":[{"amount":"10000000","denom":"uosmo"}]}"#)
+                .unwrap();
+        assert_eq!(params.denom_creation_gas_consume, None);
+        assert_eq!(params.total_create_gas(), 0);
+    }
+
+    #[test]
+    fn params_with_gas_consume_report_it() {
+        let params: TokenParams =
+            from_json(br#"{"denom_creation_fee":[],"denom_creation_gas_consume":1000000}"#)
+                .unwrap();
+        assert_eq!(params.denom_creation_gas_consume, Some(1_000_000));
+        assert_eq!(params.total_create_gas(), 1_000_000);
+    }
+
+    #[test]
+    fn target_denom_is_the_fixture_denom_except_for_create() {
+        for msg in fixtures::all_messages() {
+            let expected = match msg {
+                TokenFactoryMsg::CreateDenom { .. } => None,
+                _ => Some(fixtures::denom()),
+            };
+            assert_eq!(msg.target_denom(), expected, "{:?}", msg);
+        }
+    }
+
+    #[test]
+    fn target_denom_with_creator_resolves_create() {
+        for msg in fixtures::all_messages() {
+            assert_eq!(
+                msg.target_denom_with_creator(&fixtures::creator()),
+                fixtures::denom(),
+                "{:?}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn metadata_updated_at_queries_the_chain() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::MetadataUpdatedAt { denom } if *denom == fixtures::denom() => {
+                respond(&MetadataUpdatedAtResponse { height: 4321 })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_metadata_updated_at(fixtures::denom())
+            .unwrap();
+        assert_eq!(response.height, 4321);
+    }
+
+    #[test]
+    fn metadata_updated_at_is_unsupported_by_default() {
+        let querier = RequiredOnlyQuerier { denoms: vec![] };
+        querier
+            .query_token_factory_metadata_updated_at(fixtures::denom())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn mint_to_user_mints_to_the_user() {
+        let msg = TokenFactoryMsg::token_factory_mint_to_user(
+            fixtures::denom(),
+            1_000_000u128,
+            &fixtures::user(),
+            &fixtures::creator(),
+        )
+        .unwrap();
+        assert_eq!(msg, fixtures::mint_tokens());
+    }
+
+    #[test]
+    fn mint_to_user_rejects_a_mint_to_self() {
+        let err = TokenFactoryMsg::token_factory_mint_to_user(
+            fixtures::denom(),
+            1_000_000u128,
+            &fixtures::creator(),
+            &fixtures::creator(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::MintToSelf {
+                address: fixtures::creator().into_string(),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn query_any_wraps_each_response_in_its_variant() {
+        let querier = RequiredOnlyQuerier {
+            denoms: vec![fixtures::denom()],
+        };
+        assert_eq!(
+            querier
+                .query_any(TokenFactoryQuery::FullDenom {
+                    subdenom: fixtures::SUBDENOM.to_string(),
+                    creator_addr: fixtures::creator(),
+                })
+                .unwrap(),
+            TokenFactoryQueryResponse::FullDenom(FullDenomResponse {
+                denom: fixtures::denom(),
+            })
+        );
+        assert_eq!(
+            querier
+                .query_any(TokenFactoryQuery::DenomsByCreator {
+                    creator: fixtures::creator(),
+                })
+                .unwrap(),
+            TokenFactoryQueryResponse::DenomsByCreator(DenomsByCreatorResponse {
+                denoms: vec![fixtures::denom()],
+            })
+        );
+    }
+
+    #[test]
+    fn query_any_passes_query_errors_through() {
+        let querier = RequiredOnlyQuerier { denoms: vec![] };
+        querier
+            .query_any(TokenFactoryQuery::DenomCreationHeight {
+                denom: fixtures::denom(),
+            })
+            .unwrap_err();
+    }
+
+    #[test]
+    fn before_send_hooks_many_keeps_the_order_and_missing_hooks() {
+        let hooked = ["factory/creator/a", "factory/creator/c"];
+        let hook = fixtures::hook().into_string();
+        let querier = mock_querier(&[], move |query| match query {
+            TokenFactoryQuery::BeforeSendHookAddress { denom } => {
+                respond(&BeforeSendHookResponse {
+                    contract_addr: hooked.contains(&denom.as_str()).then(|| hook.clone()),
+                })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let hooks = querier
+            .before_send_hooks_many(vec![
+                "factory/creator/a".to_string(),
+                "factory/creator/b".to_string(),
+                "factory/creator/c".to_string(),
+            ])
+            .unwrap();
+        let addrs: Vec<Option<String>> = hooks.into_iter().map(|hook| hook.contract_addr).collect();
+        assert_eq!(
+            addrs,
+            vec![
+                Some(fixtures::hook().into_string()),
+                None,
+                Some(fixtures::hook().into_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn before_send_hook_is_unsupported_by_default() {
+        let querier = RequiredOnlyQuerier { denoms: vec![] };
+        querier
+            .query_token_factory_before_send_hook(fixtures::denom())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn revoking_the_admin_and_burning_are_irreversible() {
+        let revoke = TokenFactoryMsg::ChangeAdmin {
+            denom: fixtures::denom(),
+            new_admin_address: Addr::unchecked(""),
+        };
+        assert!(revoke.is_irreversible());
+        assert!(fixtures::burn_tokens().is_irreversible());
+    }
+
+    #[test]
+    fn handing_the_admin_over_and_minting_are_reversible() {
+        assert!(!fixtures::change_admin().is_irreversible());
+        assert!(!fixtures::mint_tokens().is_irreversible());
+        assert!(!fixtures::force_transfer().is_irreversible());
+    }
+
+    #[cfg(feature = "multi-admin")]
+    #[test]
+    fn change_admins_serializes_every_admin() {
+        let msg = TokenFactoryMsg::token_factory_change_admins(
+            fixtures::denom(),
+            vec![fixtures::creator(), fixtures::new_admin()],
+        )
+        .unwrap();
+        assert_eq!(msg, fixtures::change_admins());
+        let json = cosmwasm_std::to_json_string(&msg).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"change_admins":{{"denom":"{}","new_admins":["{}","{}"]}}}}"#,
+                fixtures::denom(),
+                fixtures::creator(),
+                fixtures::new_admin()
+            )
+        );
+    }
+
+    #[cfg(feature = "multi-admin")]
+    #[test]
+    fn admins_queries_the_chain() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::Admins { .. } => respond(&AdminsResponse {
+                admins: vec![fixtures::creator().into_string()],
+            }),
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_admins(fixtures::denom())
+            .unwrap();
+        assert_eq!(response.admins, vec![fixtures::creator().into_string()]);
+        RequiredOnlyQuerier { denoms: vec![] }
+            .query_token_factory_admins(fixtures::denom())
+            .unwrap_err();
+    }
+
+    /// Answers Metadata with the entry of `metadata` whose base is the queried denom.
+    fn metadata_querier(metadata: Vec<DenomMetadata>) -> MockQuerier<TokenFactoryQuery> {
+        mock_querier(&[], move |query| match query {
+            TokenFactoryQuery::Metadata { denom } => respond(&MetadataResponse {
+                metadata: metadata.iter().find(|m| m.base == *denom).cloned(),
+            }),
+            _ => unsupported(),
+        })
+    }
+
+    #[test]
+    fn assert_decimals_passes_on_the_expected_decimals() {
+        let querier = metadata_querier(vec![fixtures::metadata()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        querier.assert_decimals(fixtures::denom(), 6).unwrap();
+    }
+
+    #[test]
+    fn assert_decimals_fails_on_other_decimals() {
+        let querier = metadata_querier(vec![fixtures::metadata()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let err = querier.assert_decimals(fixtures::denom(), 18).unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::DecimalsMismatch {
+                expected: 18,
+                actual: Some(6),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn assert_decimals_fails_without_a_listed_display_unit() {
+        let mut unlisted = fixtures::metadata();
+        unlisted.display = "dollarx".to_string();
+        for metadata in [vec![], vec![unlisted]] {
+            let querier = metadata_querier(metadata);
+            let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+            let err = querier.assert_decimals(fixtures::denom(), 6).unwrap_err();
+            assert_eq!(
+                err,
+                TokenFactoryError::DecimalsMismatch {
+                    expected: 6,
+                    actual: None,
+                }
+                .into()
+            );
+        }
+    }
+
+    #[test]
+    fn ensure_denom_exists_passes_for_a_created_denom() {
+        let querier = denoms_querier(vec![fixtures::denom()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        querier
+            .ensure_denom_exists(fixtures::creator(), fixtures::SUBDENOM.to_string())
+            .unwrap();
+    }
+
+    #[test]
+    fn ensure_denom_exists_fails_for_a_missing_denom() {
+        let querier = denoms_querier(vec![fixtures::denom()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let err = querier
+            .ensure_denom_exists(fixtures::creator(), "other".to_string())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::DenomNotFound {
+                denom: full_denom(&fixtures::creator(), "other"),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn subdenom_available_until_created() {
+        let querier = denoms_querier(vec![fixtures::denom()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        assert!(!querier
+            .subdenom_available(fixtures::creator(), fixtures::SUBDENOM.to_string())
+            .unwrap());
+        assert!(querier
+            .subdenom_available(fixtures::creator(), "other".to_string())
+            .unwrap());
+    }
+
+    #[test]
+    fn subdenom_available_rejects_an_invalid_subdenom() {
+        let querier = denoms_querier(vec![]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        querier
+            .subdenom_available(fixtures::creator(), "not valid".to_string())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn metadata_by_ibc_hash_queries_the_chain() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::MetadataByIbcHash { ibc_hash } if ibc_hash == "27394FB0" => {
+                respond(&MetadataResponse {
+                    metadata: Some(fixtures::metadata()),
+                })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_metadata_by_ibc_hash("27394FB0".to_string())
+            .unwrap();
+        assert_eq!(response.metadata, Some(fixtures::metadata()));
+    }
+
+    #[test]
+    fn metadata_by_ibc_hash_is_unsupported_by_default() {
+        RequiredOnlyQuerier { denoms: vec![] }
+            .query_token_factory_metadata_by_ibc_hash("27394FB0".to_string())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn capability_version_covers_force_transfer_and_hooks() {
+        // Bump with the version history in the constant's doc when adding messages.
+        assert_eq!(TOKEN_FACTORY_CAPABILITY_VERSION, 2);
+    }
+
+    #[test]
+    fn metadata_or_inherit_prefers_the_denom_metadata() {
+        let lp = full_denom(&fixtures::creator(), "lp");
+        let mut own = fixtures::metadata();
+        own.base = lp.clone();
+        own.name = "LP".to_string();
+        let querier = metadata_querier(vec![own.clone(), fixtures::metadata()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        assert_eq!(
+            querier.metadata_or_inherit(lp, fixtures::denom()).unwrap(),
+            own
+        );
+    }
+
+    #[test]
+    fn metadata_or_inherit_moves_the_template_to_the_denom() {
+        let lp = full_denom(&fixtures::creator(), "lp");
+        let querier = metadata_querier(vec![fixtures::metadata()]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let metadata = querier
+            .metadata_or_inherit(lp.clone(), fixtures::denom())
+            .unwrap();
+        let mut expected = fixtures::metadata();
+        expected.base = lp.clone();
+        expected.denom_units[0] = DenomUnit {
+            denom: lp,
+            exponent: 0,
+            aliases: vec![],
+        };
+        assert_eq!(metadata, expected);
+    }
+
+    #[test]
+    fn metadata_or_inherit_fails_without_any_metadata() {
+        let lp = full_denom(&fixtures::creator(), "lp");
+        let querier = metadata_querier(vec![]);
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let err = querier
+            .metadata_or_inherit(lp.clone(), fixtures::denom())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TokenFactoryError::NoMetadataToInherit {
+                denom: lp,
+                template: fixtures::denom(),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn is_factory_denom_trusts_the_chain_when_supported() {
+        let querier = mock_querier(&[], |query| match query {
+            TokenFactoryQuery::IsFactoryDenom { .. } => {
+                respond(&IsFactoryDenomResponse { is_factory: false })
+            }
+            _ => unsupported(),
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let response = querier
+            .query_token_factory_is_factory_denom(fixtures::denom())
+            .unwrap();
+        assert!(!response.is_factory);
+    }
+
+    #[test]
+    fn is_factory_denom_falls_back_to_parsing_the_denom() {
+        let querier = mock_querier(&[], |_| unsupported());
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        assert!(
+            querier
+                .query_token_factory_is_factory_denom(fixtures::denom())
+                .unwrap()
+                .is_factory
+        );
+        assert!(
+            !querier
+                .query_token_factory_is_factory_denom("uosmo".to_string())
+                .unwrap()
+                .is_factory
+        );
+    }
+
+    #[test]
+    fn is_factory_denom_returns_errors_other_than_unsupported() {
+        let querier = mock_querier(&[], |_| {
+            SystemResult::Err(SystemError::InvalidRequest {
+                error: "malformed".to_string(),
+                request: Binary::default(),
+            })
+        });
+        let querier = QuerierWrapper::<TokenFactoryQuery>::new(&querier);
+        let err = querier
+            .query_token_factory_is_factory_denom(fixtures::denom())
+            .unwrap_err();
+        assert!(err.to_string().contains("malformed"));
+    }
+
+    #[test]
+    fn is_factory_denom_defaults_to_parsing_the_denom() {
+        let querier = RequiredOnlyQuerier { denoms: vec![] };
+        assert!(
+            querier
+                .query_token_factory_is_factory_denom(fixtures::denom())
+                .unwrap()
+                .is_factory
+        );
+        assert!(
+            !querier
+                .query_token_factory_is_factory_denom("ibc/27394FB0".to_string())
+                .unwrap()
+                .is_factory
+        );
+    }
+}
@@ -0,0 +1,732 @@
+use cosmwasm_std::{to_json_vec, Uint256};
+use sha2::{Digest, Sha256};
+
+use std::collections::BTreeSet;
+
+use crate::denom::is_valid_denom;
+use crate::{DenomMetadata, DenomUnit, TokenFactoryError};
+
+impl DenomMetadata {
+    /// Builds metadata from just a symbol and number of decimals, for prototyping.
+    ///
+    /// This is opinionated: the base unit is `u{symbol}` and the display unit `{symbol}`
+    /// (both lowercased), and the name is the symbol itself. With 0 decimals the base unit
+    /// is also the display unit. The base will not be a factory denom, so use this with
+    /// chains that accept arbitrary bases or adjust it before setting.
+    ///
+    /// Errors if the result fails [`DenomMetadata::validate`], e.g. for a symbol of fewer
+    /// than 3 characters with decimals, whose display unit is too short to be a denom.
+    pub fn from_symbol(symbol: &str, decimals: u32) -> Result<DenomMetadata, TokenFactoryError> {
+        let lowercase = symbol.to_lowercase();
+        let base = format!("u{}", lowercase);
+        let mut denom_units = vec![DenomUnit {
+            denom: base.clone(),
+            exponent: 0,
+            aliases: vec![],
+        }];
+        let display = if decimals == 0 {
+            base.clone()
+        } else {
+            denom_units.push(DenomUnit {
+                denom: lowercase.clone(),
+                exponent: decimals,
+                aliases: vec![],
+            });
+            lowercase
+        };
+        let metadata = DenomMetadata {
+            description: String::new(),
+            denom_units,
+            base,
+            display,
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+        };
+        metadata.validate()?;
+        Ok(metadata)
+    }
+
+    /// Checks the metadata against the rules the bank module enforces: non-blank name and
+    /// symbol, valid base and display denoms, a first unit equal to the base with exponent 0,
+    /// units in strictly increasing exponent order and a display unit that is listed.
+    ///
+    /// Additionally rejects an alias that appears in more than one unit or equals the
+    /// denom of another unit, since lookups by that alias would be ambiguous.
+    pub fn validate(&self) -> Result<(), TokenFactoryError> {
+        let invalid = |reason: &str| {
+            Err(TokenFactoryError::InvalidMetadata {
+                reason: reason.to_string(),
+            })
+        };
+        if self.name.trim().is_empty() {
+            return invalid("name cannot be blank");
+        }
+        if self.symbol.trim().is_empty() {
+            return invalid("symbol cannot be blank");
+        }
+        if !is_valid_denom(&self.base) {
+            return invalid("invalid base denom");
+        }
+        if !is_valid_denom(&self.display) {
+            return invalid("invalid display denom");
+        }
+        match self.denom_units.first() {
+            Some(unit) if unit.denom == self.base && unit.exponent == 0 => {}
+            _ => return invalid("first denom unit must be the base with exponent 0"),
+        }
+        if !self
+            .denom_units
+            .windows(2)
+            .all(|pair| pair[0].exponent < pair[1].exponent)
+        {
+            return invalid("denom units must be sorted by strictly increasing exponent");
+        }
+        if !self
+            .denom_units
+            .iter()
+            .any(|unit| unit.denom == self.display)
+        {
+            return invalid("display denom must be one of the denom units");
+        }
+
+        let mut names = BTreeSet::new();
+        for unit in &self.denom_units {
+            if !is_valid_denom(&unit.denom) {
+                return invalid("invalid denom unit");
+            }
+            if !names.insert(unit.denom.as_str()) {
+                return invalid("duplicate denom unit");
+            }
+        }
+        let mut aliases = BTreeSet::new();
+        for unit in &self.denom_units {
+            for alias in &unit.aliases {
+                let other_unit = names.contains(alias.as_str()) && *alias != unit.denom;
+                if other_unit || !aliases.insert(alias.as_str()) {
+                    return Err(TokenFactoryError::ConflictingAlias {
+                        alias: alias.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the compact `"base:exponent:display;name;symbol"` form, e.g.
+    /// `"factory/osmo1.../uatom:6:atom;Atom;ATOM"`, into metadata with a base unit and,
+    /// if the exponent is nonzero, a display unit. Description and aliases are left empty.
+    pub fn try_from_spec(spec: &str) -> Result<DenomMetadata, TokenFactoryError> {
+        let invalid = |reason: &str| TokenFactoryError::InvalidSpec {
+            spec: spec.to_string(),
+            reason: reason.to_string(),
+        };
+        let [units, name, symbol] = spec.split(';').collect::<Vec<_>>()[..] else {
+            return Err(invalid("expected base:exponent:display;name;symbol"));
+        };
+        // The base may contain colons, the exponent and display cannot.
+        let [display, exponent, base] = units.rsplitn(3, ':').collect::<Vec<_>>()[..] else {
+            return Err(invalid("expected base:exponent:display"));
+        };
+        let exponent: u32 = exponent
+            .parse()
+            .map_err(|_| invalid("exponent must be a non-negative integer"))?;
+
+        let mut denom_units = vec![DenomUnit {
+            denom: base.to_string(),
+            exponent: 0,
+            aliases: vec![],
+        }];
+        if exponent == 0 {
+            if display != base {
+                return Err(invalid("display must be the base when the exponent is 0"));
+            }
+        } else {
+            denom_units.push(DenomUnit {
+                denom: display.to_string(),
+                exponent,
+                aliases: vec![],
+            });
+        }
+        Ok(DenomMetadata {
+            description: String::new(),
+            denom_units,
+            base: base.to_string(),
+            display: display.to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+        })
+    }
+
+    /// Formats the metadata in the compact form parsed by [`DenomMetadata::try_from_spec`].
+    /// The description, aliases and any units other than the base and display are dropped.
+    /// Errors if the result would not parse back to the same fields: a `;` in any field, a
+    /// `:` in the display, a display unit that is not listed, or a display other than the
+    /// base with exponent 0.
+    pub fn to_spec(&self) -> Result<String, TokenFactoryError> {
+        let listed_exponent = self.display_exponent();
+        let exponent = listed_exponent.unwrap_or_default();
+        let spec = format!(
+            "{}:{}:{};{};{}",
+            self.base, exponent, self.display, self.name, self.symbol
+        );
+        let invalid = |reason: &str| TokenFactoryError::InvalidSpec {
+            spec: spec.clone(),
+            reason: reason.to_string(),
+        };
+        let fields = [&self.base, &self.display, &self.name, &self.symbol];
+        if fields.iter().any(|field| field.contains(';')) {
+            return Err(invalid("fields cannot contain ';'"));
+        }
+        if self.display.contains(':') {
+            return Err(invalid("display cannot contain ':'"));
+        }
+        if listed_exponent.is_none() {
+            return Err(invalid("display unit must be one of the denom units"));
+        }
+        if exponent == 0 && self.display != self.base {
+            return Err(invalid("display must be the base when the exponent is 0"));
+        }
+        Ok(spec)
+    }
+
+    /// Runs [`DenomMetadata::validate`] plus the conventions exchanges and wallets rely on to
+    /// display a token, returning the first failure: a symbol of 2 to 12 uppercase letters or
+    /// digits starting with a letter, a name of at most 64 characters and a display unit
+    /// with 1 to 18 decimals.
+    pub fn validate_exchange_ready(&self) -> Result<(), TokenFactoryError> {
+        self.validate()?;
+        let not_ready = |reason: &str| {
+            Err(TokenFactoryError::NotExchangeReady {
+                reason: reason.to_string(),
+            })
+        };
+        if !(2..=12).contains(&self.symbol.len()) {
+            return not_ready("symbol must be 2 to 12 characters");
+        }
+        if !self.symbol.starts_with(|c: char| c.is_ascii_uppercase())
+            || !self
+                .symbol
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        {
+            return not_ready(
+                "symbol must be uppercase letters and digits, starting with a letter",
+            );
+        }
+        if self.name.chars().count() > 64 {
+            return not_ready("name must be at most 64 characters");
+        }
+        if !self
+            .display_exponent()
+            .is_some_and(|exponent| (1..=18).contains(&exponent))
+        {
+            return not_ready("display unit must have 1 to 18 decimals");
+        }
+        Ok(())
+    }
+
+    /// Returns the exponent of the display unit, or None if the display unit is not listed.
+    pub fn display_exponent(&self) -> Option<u32> {
+        self.denom_units
+            .iter()
+            .find(|unit| unit.denom == self.display)
+            .map(|unit| unit.exponent)
+    }
+
+    /// Formats a base amount in display units, e.g. 1500000 with exponent 6 becomes "1.5 ATOM".
+    /// Uses the symbol as the unit label, falling back to the display denom if it is empty.
+    /// If the display unit is not listed, the amount is shown undivided.
+    pub fn format_amount(&self, amount: Uint256) -> String {
+        let label = if self.symbol.is_empty() {
+            &self.display
+        } else {
+            &self.symbol
+        };
+        format!(
+            "{} {}",
+            format_decimal(amount, self.display_exponent().unwrap_or_default()),
+            label
+        )
+    }
+
+    /// Returns true if both describe the token the same way on an exchange: same name,
+    /// symbol and display decimals. Description, aliases and unit names are ignored.
+    pub fn exchange_equivalent(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.symbol == other.symbol
+            && self.display_exponent() == other.display_exponent()
+    }
+
+    /// Returns true if `self` only adds to `previous`: description, base, display, name and
+    /// symbol are unchanged, and every previous unit is still present with the same exponent
+    /// and at least its previous aliases. New units and aliases are allowed, so such a change
+    /// cannot break anyone relying on the previous metadata. Identical metadata is additive.
+    pub fn is_additive_change(&self, previous: &Self) -> bool {
+        let scalars_unchanged = self.description == previous.description
+            && self.base == previous.base
+            && self.display == previous.display
+            && self.name == previous.name
+            && self.symbol == previous.symbol;
+        scalars_unchanged
+            && previous.denom_units.iter().all(|old| {
+                self.denom_units.iter().any(|new| {
+                    new.denom == old.denom
+                        && new.exponent == old.exponent
+                        && old.aliases.iter().all(|alias| new.aliases.contains(alias))
+                })
+            })
+    }
+
+    /// Puts the metadata in canonical form so logically equal metadata compare equal:
+    /// units sorted by exponent (then denom), aliases sorted with duplicates and empty
+    /// aliases removed.
+    pub fn canonicalize(&mut self) {
+        self.denom_units
+            .sort_by(|a, b| (a.exponent, &a.denom).cmp(&(b.exponent, &b.denom)));
+        for unit in &mut self.denom_units {
+            unit.aliases.retain(|alias| !alias.is_empty());
+            unit.aliases.sort();
+            unit.aliases.dedup();
+        }
+    }
+
+    /// Returns a canonicalized copy, see [`DenomMetadata::canonicalize`].
+    pub fn canonical(&self) -> DenomMetadata {
+        let mut metadata = self.clone();
+        metadata.canonicalize();
+        metadata
+    }
+
+    /// Returns the SHA-256 of the canonical JSON serialization, to detect metadata changes
+    /// without storing the full metadata.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let bytes = to_json_vec(&self.canonical()).expect("metadata is always serializable");
+        Sha256::digest(bytes).into()
+    }
+
+    /// Serializes the canonicalized metadata as compact JSON with object keys in
+    /// lexicographic order, independent of serde's field order or `serde_json` features,
+    /// so equal metadata always produces the same bytes.
+    #[cfg(feature = "json")]
+    pub fn to_canonical_json(&self) -> String {
+        let value =
+            serde_json::to_value(self.canonical()).expect("metadata is always serializable");
+        let mut out = String::new();
+        write_canonical_json(&value, &mut out);
+        out
+    }
+
+    /// Applies an RFC 7386 JSON Merge Patch to the metadata fields.
+    ///
+    /// `denom_units` is replaced as a whole, never merged unit by unit. Unknown fields,
+    /// removing a field with `null` or a value of the wrong type fail the whole patch,
+    /// in which case the metadata is left unchanged.
+    #[cfg(feature = "json")]
+    pub fn apply_merge_patch(
+        &mut self,
+        patch: &serde_json::Value,
+    ) -> Result<(), TokenFactoryError> {
+        let patch = patch
+            .as_object()
+            .ok_or_else(|| TokenFactoryError::InvalidMetadataPatch {
+                reason: "patch must be a JSON object".to_string(),
+            })?;
+        let mut target = serde_json::to_value(&*self).map_err(|err| {
+            TokenFactoryError::InvalidMetadataPatch {
+                reason: err.to_string(),
+            }
+        })?;
+        let fields = target
+            .as_object_mut()
+            .expect("metadata serializes to an object");
+        for (key, value) in patch {
+            if value.is_null() {
+                fields.remove(key);
+            } else {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+        *self = serde_json::from_value(target).map_err(|err| {
+            TokenFactoryError::InvalidMetadataPatch {
+                reason: err.to_string(),
+            }
+        })?;
+        Ok(())
+    }
+}
+
+/// Renders `amount / 10^exponent` without trailing fractional zeros.
+fn format_decimal(amount: Uint256, exponent: u32) -> String {
+    let digits = amount.to_string();
+    let exponent = exponent as usize;
+    if exponent == 0 {
+        return digits;
+    }
+    let padded = format!("{:0>width$}", digits, width = exponent + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - exponent);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+#[cfg(feature = "json")]
+fn write_canonical_json(value: &serde_json::Value, out: &mut String) {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, DenomMetadata, DenomUnit, TokenFactoryError};
+
+    #[test]
+    fn exchange_equivalent_ignores_description_and_aliases() {
+        let mut other = fixtures::metadata();
+        other.description = "Another description".to_string();
+        other.denom_units[0].aliases.clear();
+        assert!(fixtures::metadata().exchange_equivalent(&other));
+    }
+
+    #[test]
+    fn exchange_equivalent_compares_name_symbol_and_decimals() {
+        let mut renamed = fixtures::metadata();
+        renamed.symbol = "USDY".to_string();
+        assert!(!fixtures::metadata().exchange_equivalent(&renamed));
+
+        let mut decimals = fixtures::metadata();
+        decimals.denom_units[1].exponent = 18;
+        assert!(!fixtures::metadata().exchange_equivalent(&decimals));
+    }
+
+    #[test]
+    fn from_symbol_derives_units_and_names() {
+        let metadata = DenomMetadata::from_symbol("ATOM", 6).unwrap();
+        assert_eq!(
+            metadata,
+            DenomMetadata {
+                description: String::new(),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: "uatom".to_string(),
+                        exponent: 0,
+                        aliases: vec![],
+                    },
+                    DenomUnit {
+                        denom: "atom".to_string(),
+                        exponent: 6,
+                        aliases: vec![],
+                    },
+                ],
+                base: "uatom".to_string(),
+                display: "atom".to_string(),
+                name: "ATOM".to_string(),
+                symbol: "ATOM".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn from_symbol_without_decimals_displays_the_base() {
+        let metadata = DenomMetadata::from_symbol("PTS", 0).unwrap();
+        assert_eq!(metadata.denom_units.len(), 1);
+        assert_eq!(metadata.display, "upts");
+        assert_eq!(metadata.base, "upts");
+    }
+
+    #[test]
+    fn from_symbol_rejects_a_display_unit_too_short_for_a_denom() {
+        assert_eq!(
+            DenomMetadata::from_symbol("OP", 18).unwrap_err(),
+            TokenFactoryError::InvalidMetadata {
+                reason: "invalid display denom".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn validate_rejects_an_alias_shared_by_two_units() {
+        let mut metadata = fixtures::metadata();
+        metadata.denom_units[1].aliases = vec!["microusdx".to_string()];
+        assert_eq!(
+            metadata.validate().unwrap_err(),
+            TokenFactoryError::ConflictingAlias {
+                alias: "microusdx".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn validate_rejects_an_alias_naming_another_unit() {
+        let mut metadata = fixtures::metadata();
+        metadata.denom_units[0].aliases = vec!["usdx".to_string()];
+        assert_eq!(
+            metadata.validate().unwrap_err(),
+            TokenFactoryError::ConflictingAlias {
+                alias: "usdx".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn validate_accepts_distinct_aliases() {
+        let mut metadata = fixtures::metadata();
+        metadata.denom_units[1].aliases = vec!["dollarx".to_string()];
+        metadata.validate().unwrap();
+    }
+
+    fn reordered() -> DenomMetadata {
+        let mut metadata = fixtures::metadata();
+        metadata.denom_units.reverse();
+        metadata.denom_units[1].aliases = vec![
+            "microusdx".to_string(),
+            String::new(),
+            "microusdx".to_string(),
+        ];
+        metadata
+    }
+
+    #[test]
+    fn canonical_forms_of_reordered_metadata_are_equal() {
+        assert_ne!(reordered(), fixtures::metadata());
+        assert_eq!(reordered().canonical(), fixtures::metadata().canonical());
+    }
+
+    #[test]
+    fn canonicalize_sorts_units_by_exponent() {
+        let mut metadata = reordered();
+        metadata.canonicalize();
+        assert_eq!(metadata, fixtures::metadata());
+    }
+
+    #[test]
+    fn fingerprint_is_equal_for_logically_equal_metadata() {
+        assert_eq!(
+            reordered().fingerprint(),
+            fixtures::metadata().fingerprint()
+        );
+    }
+
+    #[test]
+    fn fingerprint_changes_with_any_field() {
+        let mut renamed = fixtures::metadata();
+        renamed.name = "USD Example v2".to_string();
+        assert_ne!(renamed.fingerprint(), fixtures::metadata().fingerprint());
+
+        let mut aliased = fixtures::metadata();
+        aliased.denom_units[1].aliases.push("dollarx".to_string());
+        assert_ne!(aliased.fingerprint(), fixtures::metadata().fingerprint());
+    }
+
+    #[test]
+    fn spec_round_trips_the_base_and_display() {
+        let spec = fixtures::metadata().to_spec().unwrap();
+        assert_eq!(
+            spec,
+            format!("{}:6:usdx;USD Example;USDX", fixtures::denom())
+        );
+        let mut expected = fixtures::metadata();
+        expected.description.clear();
+        expected.denom_units[0].aliases.clear();
+        assert_eq!(DenomMetadata::try_from_spec(&spec).unwrap(), expected);
+    }
+
+    #[test]
+    fn try_from_spec_rejects_malformed_specs() {
+        for spec in [
+            "uatom:6:atom;Atom",
+            "atom;Atom;ATOM",
+            "uatom:-1:atom;Atom;ATOM",
+            "uatom:0:atom;Atom;ATOM",
+        ] {
+            assert!(
+                matches!(
+                    DenomMetadata::try_from_spec(spec),
+                    Err(TokenFactoryError::InvalidSpec { .. })
+                ),
+                "{}",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn to_spec_rejects_metadata_it_cannot_hold() {
+        let mut semicolon = fixtures::metadata();
+        semicolon.name = "USD; Example".to_string();
+        let mut unlisted = fixtures::metadata();
+        unlisted.display = "dollarx".to_string();
+        for metadata in [semicolon, unlisted] {
+            assert!(matches!(
+                metadata.to_spec(),
+                Err(TokenFactoryError::InvalidSpec { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn exchange_ready_rejects_each_broken_convention() {
+        let mut long_symbol = fixtures::metadata();
+        long_symbol.symbol = "USDXUSDXUSDXU".to_string();
+        let mut lowercase_symbol = fixtures::metadata();
+        lowercase_symbol.symbol = "usdx".to_string();
+        let mut digit_first = fixtures::metadata();
+        digit_first.symbol = "1USD".to_string();
+        let mut long_name = fixtures::metadata();
+        long_name.name = "x".repeat(65);
+        let mut no_decimals = fixtures::metadata();
+        no_decimals.display = fixtures::denom();
+        for metadata in [
+            long_symbol,
+            lowercase_symbol,
+            digit_first,
+            long_name,
+            no_decimals,
+        ] {
+            assert!(
+                matches!(
+                    metadata.validate_exchange_ready(),
+                    Err(TokenFactoryError::NotExchangeReady { .. })
+                ),
+                "{:?}",
+                metadata
+            );
+        }
+    }
+
+    #[test]
+    fn adding_units_and_aliases_is_additive() {
+        let mut next = fixtures::metadata();
+        next.denom_units[1].aliases.push("dollarx".to_string());
+        next.denom_units.push(DenomUnit {
+            denom: "kusdx".to_string(),
+            exponent: 9,
+            aliases: vec![],
+        });
+        assert!(next.is_additive_change(&fixtures::metadata()));
+    }
+
+    #[test]
+    fn changing_a_scalar_or_removing_a_unit_is_not_additive() {
+        let mut renamed = fixtures::metadata();
+        renamed.symbol = "USDY".to_string();
+        assert!(!renamed.is_additive_change(&fixtures::metadata()));
+
+        let mut unaliased = fixtures::metadata();
+        unaliased.denom_units[0].aliases.clear();
+        assert!(!unaliased.is_additive_change(&fixtures::metadata()));
+
+        let mut removed = fixtures::metadata();
+        removed.denom_units.pop();
+        assert!(!removed.is_additive_change(&fixtures::metadata()));
+
+        let mut rescaled = fixtures::metadata();
+        rescaled.denom_units[1].exponent = 18;
+        assert!(!rescaled.is_additive_change(&fixtures::metadata()));
+    }
+
+    #[cfg(feature = "json")]
+    mod json {
+        use crate::{fixtures, DenomMetadata, DenomUnit, TokenFactoryError};
+
+        #[test]
+        fn merge_patch_replaces_scalars() {
+            let mut metadata = fixtures::metadata();
+            let patch = serde_json::json!({ "name": "USD Example v2", "description": "Renamed" });
+            metadata.apply_merge_patch(&patch).unwrap();
+            assert_eq!(metadata.name, "USD Example v2");
+            assert_eq!(metadata.description, "Renamed");
+            assert_eq!(metadata.symbol, fixtures::metadata().symbol);
+        }
+
+        #[test]
+        fn merge_patch_replaces_units_as_a_whole() {
+            let mut metadata = fixtures::metadata();
+            let patch = serde_json::json!({
+                "denom_units": [{ "denom": fixtures::denom(), "exponent": 0, "aliases": [] }],
+                "display": fixtures::denom(),
+            });
+            metadata.apply_merge_patch(&patch).unwrap();
+            assert_eq!(
+                metadata.denom_units,
+                vec![DenomUnit {
+                    denom: fixtures::denom(),
+                    exponent: 0,
+                    aliases: vec![],
+                }]
+            );
+            assert_eq!(metadata.display, fixtures::denom());
+        }
+
+        #[test]
+        fn invalid_merge_patch_leaves_metadata_unchanged() {
+            let mut metadata = fixtures::metadata();
+            for patch in [
+                serde_json::json!({ "name": null }),
+                serde_json::json!({ "unknown": "field" }),
+                serde_json::json!({ "denom_units": "not a list" }),
+                serde_json::json!("not an object"),
+            ] {
+                let err = metadata.apply_merge_patch(&patch).unwrap_err();
+                assert!(matches!(
+                    err,
+                    TokenFactoryError::InvalidMetadataPatch { .. }
+                ));
+                assert_eq!(metadata, fixtures::metadata());
+            }
+        }
+
+        #[test]
+        fn canonical_json_sorts_keys() {
+            let json = DenomMetadata::from_symbol("ATOM", 6)
+                .unwrap()
+                .to_canonical_json();
+            assert_eq!(
+                json,
+                concat!(
+                    r#"{"base":"uatom","denom_units":["#,
+                    r#"{"aliases":[],"denom":"uatom","exponent":0},"#,
+                    r#"{"aliases":[],"denom":"atom","exponent":6}],"#,
+                    r#""description":"","display":"atom","name":"ATOM","symbol":"ATOM"}"#,
+                )
+            );
+        }
+
+        #[test]
+        fn canonical_json_is_equal_for_logically_equal_metadata() {
+            assert_eq!(
+                super::reordered().to_canonical_json(),
+                fixtures::metadata().to_canonical_json()
+            );
+        }
+    }
+}
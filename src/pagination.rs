@@ -0,0 +1,141 @@
+use std::fmt;
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, StdError};
+
+/// A pagination key, displayed and parsed as base64 so it can be passed around as text,
+/// e.g. on a command line.
+#[cw_serde]
+pub struct PageKey(pub Binary);
+
+impl fmt::Display for PageKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_base64())
+    }
+}
+
+impl FromStr for PageKey {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PageKey(Binary::from_base64(s)?))
+    }
+}
+
+impl From<Binary> for PageKey {
+    fn from(key: Binary) -> Self {
+        PageKey(key)
+    }
+}
+
+impl From<PageKey> for Binary {
+    fn from(key: PageKey) -> Self {
+        key.0
+    }
+}
+
+/// Pagination of a list query, mirroring cosmos-sdk's PageRequest.
+#[cw_serde]
+#[derive(Default)]
+pub struct PageRequest {
+    /// Key returned as `next_key` by the previous page. None starts from the beginning.
+    pub key: Option<Binary>,
+    /// Maximum number of results. None uses the chain's default.
+    pub limit: Option<u64>,
+    /// Asks the chain to return the total number of results.
+    #[serde(default)]
+    pub count_total: bool,
+    /// Returns results in descending order.
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+impl PageRequest {
+    /// Requests the first page of at most `limit` results.
+    pub fn with_limit(limit: u64) -> Self {
+        PageRequest {
+            limit: Some(limit),
+            ..PageRequest::default()
+        }
+    }
+
+    /// Requests at most `limit` results starting at `key`, the `next_key` of the previous page.
+    pub fn after(key: PageKey, limit: u64) -> Self {
+        PageRequest {
+            key: Some(key.into()),
+            limit: Some(limit),
+            ..PageRequest::default()
+        }
+    }
+}
+
+/// Pagination info of a list query response, mirroring cosmos-sdk's PageResponse.
+#[cw_serde]
+pub struct PageResponse {
+    /// Key to request the next page with. None if this is the last page.
+    pub next_key: Option<Binary>,
+    /// Total number of results, if it was requested.
+    pub total: Option<u64>,
+}
+
+impl PageResponse {
+    /// Returns the key of the next page, or None if this is the last page.
+    pub fn next_page_key(&self) -> Option<PageKey> {
+        self.next_key.clone().map(PageKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_key_round_trips_through_base64() {
+        let key = PageKey(Binary::from(b"creator42".to_vec()));
+        let encoded = key.to_string();
+        assert_eq!(encoded, "Y3JlYXRvcjQy");
+        assert_eq!(encoded.parse::<PageKey>().unwrap(), key);
+        "not base64!".parse::<PageKey>().unwrap_err();
+    }
+
+    #[test]
+    fn page_requests_set_the_key_and_limit() {
+        assert_eq!(
+            PageRequest::with_limit(10),
+            PageRequest {
+                key: None,
+                limit: Some(10),
+                count_total: false,
+                reverse: false,
+            }
+        );
+        let key = PageKey(Binary::from(b"next".to_vec()));
+        assert_eq!(
+            PageRequest::after(key.clone(), 10),
+            PageRequest {
+                key: Some(key.0),
+                limit: Some(10),
+                count_total: false,
+                reverse: false,
+            }
+        );
+    }
+
+    #[test]
+    fn next_page_key_is_none_on_the_last_page() {
+        let last = PageResponse {
+            next_key: None,
+            total: None,
+        };
+        assert_eq!(last.next_page_key(), None);
+        let more = PageResponse {
+            next_key: Some(Binary::from(b"next".to_vec())),
+            total: Some(3),
+        };
+        assert_eq!(
+            more.next_page_key(),
+            Some(PageKey(Binary::from(b"next".to_vec())))
+        );
+    }
+}
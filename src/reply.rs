@@ -0,0 +1,106 @@
+use cosmwasm_std::{CosmosMsg, Reply, StdResult, SubMsg};
+
+use crate::{DenomMetadata, TokenFactoryError, TokenFactoryMsg};
+
+/// Event emitted by the token factory module when a denom is created.
+pub const CREATE_DENOM_EVENT: &str = "create_denom";
+/// Attribute of [`CREATE_DENOM_EVENT`] holding the created denom.
+pub const NEW_TOKEN_DENOM_ATTRIBUTE: &str = "new_token_denom";
+
+/// Wraps a CreateDenom in a submessage that replies with `reply_id` on success,
+/// so the created denom can be read with [`parse_create_denom_reply`].
+///
+/// Attaching a payload to the submessage, to read it back from the reply, needs
+/// cosmwasm-std 2. With the 1.x versions this crate supports, store whatever context the
+/// reply needs under `reply_id` before sending, and read the denom from the reply events.
+pub fn token_factory_create_denom_submsg(
+    subdenom: String,
+    metadata: Option<DenomMetadata>,
+    reply_id: u64,
+) -> SubMsg<TokenFactoryMsg> {
+    SubMsg::reply_on_success(
+        CosmosMsg::Custom(TokenFactoryMsg::CreateDenom { subdenom, metadata }),
+        reply_id,
+    )
+}
+
+/// Returns the denom created by a CreateDenom submessage, read from the `new_token_denom`
+/// attribute of the `create_denom` event of its reply.
+pub fn parse_create_denom_reply(reply: Reply) -> StdResult<String> {
+    let response = reply
+        .result
+        .into_result()
+        .map_err(|reason| TokenFactoryError::CreateDenomFailed { reason })?;
+    response
+        .events
+        .iter()
+        .filter(|event| event.ty == CREATE_DENOM_EVENT)
+        .flat_map(|event| &event.attributes)
+        .find(|attribute| attribute.key == NEW_TOKEN_DENOM_ATTRIBUTE)
+        .map(|attribute| attribute.value.clone())
+        .ok_or_else(|| TokenFactoryError::NoCreatedDenom {}.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Event, ReplyOn, SubMsgResponse, SubMsgResult};
+
+    use super::*;
+
+    fn reply(result: SubMsgResult) -> Reply {
+        Reply { id: 7, result }
+    }
+
+    #[test]
+    fn create_denom_submsg_replies_on_success() {
+        let submsg = token_factory_create_denom_submsg("uusdx".to_string(), None, 7);
+        assert_eq!(submsg.id, 7);
+        assert_eq!(submsg.reply_on, ReplyOn::Success);
+        assert_eq!(
+            submsg.msg,
+            CosmosMsg::Custom(TokenFactoryMsg::CreateDenom {
+                subdenom: "uusdx".to_string(),
+                metadata: None,
+            })
+        );
+    }
+
+    #[test]
+    fn create_denom_reply_yields_the_new_denom() {
+        let events = vec![
+            Event::new("message").add_attribute("module", "tokenfactory"),
+            Event::new(CREATE_DENOM_EVENT)
+                .add_attribute("creator", "creator")
+                .add_attribute(NEW_TOKEN_DENOM_ATTRIBUTE, "factory/creator/uusdx"),
+        ];
+        let result = SubMsgResult::Ok(SubMsgResponse { events, data: None });
+        assert_eq!(
+            parse_create_denom_reply(reply(result)).unwrap(),
+            "factory/creator/uusdx"
+        );
+    }
+
+    #[test]
+    fn create_denom_reply_without_the_event_fails() {
+        let result = SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        });
+        assert_eq!(
+            parse_create_denom_reply(reply(result)).unwrap_err(),
+            TokenFactoryError::NoCreatedDenom {}.into()
+        );
+    }
+
+    #[test]
+    fn failed_create_denom_reply_fails() {
+        let result = SubMsgResult::Err("denom already exists".to_string());
+        assert_eq!(
+            parse_create_denom_reply(reply(result)).unwrap_err(),
+            TokenFactoryError::CreateDenomFailed {
+                reason: "denom already exists".to_string(),
+            }
+            .into()
+        );
+    }
+}
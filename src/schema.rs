@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use cosmwasm_schema::schema_for;
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::{TokenFactoryError, TokenFactoryMsg};
+
+struct MsgValidators {
+    root: Validator,
+    /// One validator per message variant, keyed by its JSON name, so errors point at the
+    /// offending field instead of only reporting that no variant matched.
+    variants: BTreeMap<String, Validator>,
+}
+
+fn msg_validators() -> &'static MsgValidators {
+    static VALIDATORS: OnceLock<MsgValidators> = OnceLock::new();
+    VALIDATORS.get_or_init(|| {
+        let schema = serde_json::to_value(schema_for!(TokenFactoryMsg))
+            .expect("schema is always serializable");
+        let definitions = schema.get("definitions").cloned().unwrap_or_default();
+        let variants = schema["oneOf"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|variant| {
+                let name = variant["required"][0].as_str()?.to_string();
+                let mut variant = variant.clone();
+                variant["definitions"] = definitions.clone();
+                let validator = jsonschema::validator_for(&variant).ok()?;
+                Some((name, validator))
+            })
+            .collect();
+        MsgValidators {
+            root: jsonschema::validator_for(&schema).expect("generated schema is valid"),
+            variants,
+        }
+    })
+}
+
+/// Validates arbitrary JSON against the generated TokenFactoryMsg schema and deserializes it.
+/// Every schema violation is reported with the path of the offending value.
+pub fn validate_msg_json(value: &Value) -> Result<TokenFactoryMsg, TokenFactoryError> {
+    let validators = msg_validators();
+    let validator = match value.as_object() {
+        Some(msg) if msg.len() == 1 => msg
+            .keys()
+            .next()
+            .and_then(|name| validators.variants.get(name))
+            .unwrap_or(&validators.root),
+        _ => &validators.root,
+    };
+    let errors: Vec<String> = validator
+        .iter_errors(value)
+        .map(|err| format!("{} at \"{}\"", err, err.instance_path()))
+        .collect();
+    if !errors.is_empty() {
+        return Err(TokenFactoryError::InvalidMsgJson {
+            reason: errors.join("; "),
+        });
+    }
+    serde_json::from_value(value.clone()).map_err(|err| TokenFactoryError::InvalidMsgJson {
+        reason: err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn valid_json_deserializes() {
+        let value = serde_json::to_value(fixtures::mint_tokens()).unwrap();
+        assert_eq!(validate_msg_json(&value).unwrap(), fixtures::mint_tokens());
+    }
+
+    #[test]
+    fn invalid_json_reports_the_offending_field() {
+        let value = json!({ "mint_tokens": {
+            "denom": fixtures::denom(),
+            "amount": 5,
+            "mint_to_address": fixtures::user(),
+        }});
+        let err = validate_msg_json(&value).unwrap_err();
+        assert!(
+            matches!(&err, TokenFactoryError::InvalidMsgJson { reason } if reason.contains("/mint_tokens/amount")),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn unknown_variant_is_rejected() {
+        validate_msg_json(&json!({ "mint_everything": {} })).unwrap_err();
+    }
+}
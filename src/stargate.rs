@@ -0,0 +1,421 @@
+use std::any::type_name;
+
+use cosmwasm_std::{
+    to_json_vec, Addr, ContractResult, CustomQuery, Empty, QuerierWrapper, QueryRequest, StdError,
+    StdResult, SystemResult, Uint128, Uint256,
+};
+use prost::Message;
+
+use crate::{
+    full_denom, AdminResponse, AllCreatorsResponse, BeforeSendHookResponse, DenomCreationFee,
+    DenomCreationHeightResponse, DenomMetadata, DenomUnit, DenomsByCreatorResponse,
+    FullDenomResponse, MetadataResponse, MetadataUpdatedAtResponse, PageRequest,
+    TokenFactoryBankQuerier, TokenFactoryQuerier, TokenParams, TokenParamsResponse,
+};
+
+/// Proto package of the token factory module on Osmosis and chains forked from it.
+pub const OSMOSIS_TOKEN_FACTORY_PACKAGE: &str = "osmosis.tokenfactory.v1beta1";
+
+const BANK_DENOM_METADATA_PATH: &str = "/cosmos.bank.v1beta1.Query/DenomMetadata";
+
+/// A TokenFactoryQuerier for chains that serve token factory queries over gRPC
+/// (`QueryRequest::Stargate` in cosmwasm-std 1.x) instead of as custom queries.
+///
+/// The full denom is computed locally and metadata comes from the bank module, where any
+/// error answer counts as no metadata.
+/// Queries without a gRPC counterpart (creation height, all creators, metadata update
+/// height, metadata by IBC hash, admins) return an error, the denom count falls back to
+/// counting DenomsFromCreator and IsFactoryDenom to parsing the denom.
+pub struct StargateTokenFactoryQuerier<'a, C: CustomQuery = Empty> {
+    querier: QuerierWrapper<'a, C>,
+    package: String,
+}
+
+impl<'a, C: CustomQuery> StargateTokenFactoryQuerier<'a, C> {
+    /// Queries the Osmosis token factory package.
+    pub fn new(querier: QuerierWrapper<'a, C>) -> Self {
+        Self::with_package(querier, OSMOSIS_TOKEN_FACTORY_PACKAGE)
+    }
+
+    /// Queries a token factory served under another proto package,
+    /// e.g. `injective.tokenfactory.v1beta1`.
+    pub fn with_package(querier: QuerierWrapper<'a, C>, package: impl Into<String>) -> Self {
+        StargateTokenFactoryQuerier {
+            querier,
+            package: package.into(),
+        }
+    }
+
+    fn path(&self, method: &str) -> String {
+        format!("/{}.Query/{}", self.package, method)
+    }
+
+    fn query_proto<Res: Message + Default>(
+        &self,
+        path: String,
+        request: &impl Message,
+    ) -> StdResult<Res> {
+        match self.query_proto_result(path, request)? {
+            ContractResult::Err(err) => Err(StdError::generic_err(format!(
+                "Querier contract error: {}",
+                err
+            ))),
+            ContractResult::Ok(response) => Ok(response),
+        }
+    }
+
+    /// Like `query_proto`, but returns an error answer of the queried module as
+    /// `ContractResult::Err` instead of failing.
+    fn query_proto_result<Res: Message + Default>(
+        &self,
+        path: String,
+        request: &impl Message,
+    ) -> StdResult<ContractResult<Res>> {
+        let request = to_json_vec(&QueryRequest::<Empty>::Stargate {
+            path,
+            data: request.encode_to_vec().into(),
+        })?;
+        match self.querier.raw_query(&request) {
+            SystemResult::Err(err) => Err(StdError::generic_err(format!(
+                "Querier system error: {}",
+                err
+            ))),
+            SystemResult::Ok(ContractResult::Err(err)) => Ok(ContractResult::Err(err)),
+            SystemResult::Ok(ContractResult::Ok(data)) => Res::decode(data.as_slice())
+                .map(ContractResult::Ok)
+                .map_err(|err| StdError::parse_err(type_name::<Res>(), err)),
+        }
+    }
+}
+
+impl<'a, C: CustomQuery> TokenFactoryQuerier for StargateTokenFactoryQuerier<'a, C> {
+    fn query_token_factory_full_denom(
+        &self,
+        subdenom: String,
+        creator_addr: Addr,
+    ) -> StdResult<FullDenomResponse> {
+        Ok(FullDenomResponse {
+            denom: full_denom(&creator_addr, &subdenom),
+        })
+    }
+
+    fn query_token_factory_admin(&self, denom: String) -> StdResult<AdminResponse> {
+        let response: proto::QueryDenomAuthorityMetadataResponse = self.query_proto(
+            self.path("DenomAuthorityMetadata"),
+            &proto::QueryDenomAuthorityMetadataRequest { denom },
+        )?;
+        Ok(AdminResponse {
+            admin: response
+                .authority_metadata
+                .map(|metadata| metadata.admin)
+                .unwrap_or_default(),
+        })
+    }
+
+    fn query_token_factory_metadata(&self, denom: String) -> StdResult<MetadataResponse> {
+        let response: ContractResult<proto::QueryDenomMetadataResponse> = self.query_proto_result(
+            BANK_DENOM_METADATA_PATH.to_string(),
+            &proto::QueryDenomMetadataRequest { denom },
+        )?;
+        match response {
+            ContractResult::Ok(response) => Ok(MetadataResponse {
+                metadata: response.metadata.map(Into::into),
+            }),
+            // The bank module answers NotFound for denoms without metadata, but wasmd redacts
+            // gRPC errors to "codespace: ..., code: ..." before they reach the contract, so
+            // the cause cannot be told apart reliably. Any error answer counts as no metadata;
+            // failures of the querier itself still error.
+            ContractResult::Err(_) => Ok(MetadataResponse { metadata: None }),
+        }
+    }
+
+    fn query_token_factory_denoms_by_creator(
+        &self,
+        creator: Addr,
+    ) -> StdResult<DenomsByCreatorResponse> {
+        let response: proto::QueryDenomsFromCreatorResponse = self.query_proto(
+            self.path("DenomsFromCreator"),
+            &proto::QueryDenomsFromCreatorRequest {
+                creator: creator.into_string(),
+            },
+        )?;
+        Ok(DenomsByCreatorResponse {
+            denoms: response.denoms,
+        })
+    }
+
+    fn query_token_factory_params(&self) -> StdResult<TokenParamsResponse> {
+        let response: proto::QueryParamsResponse =
+            self.query_proto(self.path("Params"), &proto::QueryParamsRequest {})?;
+        let params = response.params.unwrap_or_default();
+        Ok(TokenParamsResponse {
+            params: TokenParams {
+                denom_creation_fee: params
+                    .denom_creation_fee
+                    .into_iter()
+                    .map(|coin| {
+                        Ok(DenomCreationFee {
+                            amount: coin.amount.parse::<Uint256>()?,
+                            denom: coin.denom,
+                        })
+                    })
+                    .collect::<StdResult<_>>()?,
+                denom_creation_gas_consume: Some(params.denom_creation_gas_consume),
+                max_subdenom_length: None,
+            },
+        })
+    }
+
+    fn query_token_factory_denom_creation_height(
+        &self,
+        _denom: String,
+    ) -> StdResult<DenomCreationHeightResponse> {
+        Err(StdError::generic_err(
+            "DenomCreationHeight has no gRPC counterpart",
+        ))
+    }
+
+    fn query_token_factory_all_creators(
+        &self,
+        _pagination: Option<PageRequest>,
+    ) -> StdResult<AllCreatorsResponse> {
+        Err(StdError::generic_err("AllCreators has no gRPC counterpart"))
+    }
+
+    fn query_token_factory_metadata_updated_at(
+        &self,
+        _denom: String,
+    ) -> StdResult<MetadataUpdatedAtResponse> {
+        Err(StdError::generic_err(
+            "MetadataUpdatedAt has no gRPC counterpart",
+        ))
+    }
+
+    fn query_token_factory_before_send_hook(
+        &self,
+        denom: String,
+    ) -> StdResult<BeforeSendHookResponse> {
+        let response: proto::QueryBeforeSendHookAddressResponse = self.query_proto(
+            self.path("BeforeSendHookAddress"),
+            &proto::QueryBeforeSendHookAddressRequest { denom },
+        )?;
+        Ok(BeforeSendHookResponse {
+            contract_addr: Some(response.cosmwasm_address).filter(|addr| !addr.is_empty()),
+        })
+    }
+
+    fn query_token_factory_metadata_by_ibc_hash(
+        &self,
+        _ibc_hash: String,
+    ) -> StdResult<MetadataResponse> {
+        Err(StdError::generic_err(
+            "MetadataByIbcHash has no gRPC counterpart",
+        ))
+    }
+
+    #[cfg(feature = "multi-admin")]
+    fn query_token_factory_admins(&self, _denom: String) -> StdResult<crate::AdminsResponse> {
+        Err(StdError::generic_err("Admins has no gRPC counterpart"))
+    }
+}
+
+impl<'a, C: CustomQuery> TokenFactoryBankQuerier for StargateTokenFactoryQuerier<'a, C> {
+    fn bank_querier(&self) -> QuerierWrapper<'_, Empty> {
+        QuerierWrapper::new(&*self.querier)
+    }
+}
+
+/// Converts a mint amount into the cosmos-sdk proto `Coin`, for events consumed by indexers
+/// that expect the proto form. Errors if the amount does not fit a `Uint128`, like a bank coin.
+pub fn mint_coin_proto(denom: impl Into<String>, amount: Uint256) -> StdResult<proto::Coin> {
+    Ok(proto::Coin {
+        denom: denom.into(),
+        amount: Uint128::try_from(amount)?.to_string(),
+    })
+}
+
+impl From<proto::Metadata> for DenomMetadata {
+    fn from(metadata: proto::Metadata) -> Self {
+        DenomMetadata {
+            description: metadata.description,
+            denom_units: metadata
+                .denom_units
+                .into_iter()
+                .map(|unit| DenomUnit {
+                    denom: unit.denom,
+                    exponent: unit.exponent,
+                    aliases: unit.aliases,
+                })
+                .collect(),
+            base: metadata.base,
+            display: metadata.display,
+            name: metadata.name,
+            symbol: metadata.symbol,
+        }
+    }
+}
+
+/// The subset of the token factory protos needed by the querier. The bank and coin types
+/// are re-exported from cosmos-sdk-proto.
+pub mod proto {
+    pub use cosmos_sdk_proto::cosmos::bank::v1beta1::{
+        DenomUnit, Metadata, QueryDenomMetadataRequest, QueryDenomMetadataResponse,
+    };
+    pub use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryDenomAuthorityMetadataRequest {
+        #[prost(string, tag = "1")]
+        pub denom: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct DenomAuthorityMetadata {
+        #[prost(string, tag = "1")]
+        pub admin: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryDenomAuthorityMetadataResponse {
+        #[prost(message, optional, tag = "1")]
+        pub authority_metadata: Option<DenomAuthorityMetadata>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryDenomsFromCreatorRequest {
+        #[prost(string, tag = "1")]
+        pub creator: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryDenomsFromCreatorResponse {
+        #[prost(string, repeated, tag = "1")]
+        pub denoms: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryBeforeSendHookAddressRequest {
+        #[prost(string, tag = "1")]
+        pub denom: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryBeforeSendHookAddressResponse {
+        #[prost(string, tag = "1")]
+        pub cosmwasm_address: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryParamsRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Params {
+        #[prost(message, repeated, tag = "1")]
+        pub denom_creation_fee: Vec<Coin>,
+        #[prost(uint64, tag = "2")]
+        pub denom_creation_gas_consume: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QueryParamsResponse {
+        #[prost(message, optional, tag = "1")]
+        pub params: Option<Params>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_json, Binary, Querier, QuerierResult};
+
+    use super::*;
+    use crate::fixtures;
+
+    /// Answers gRPC queries with `handler(path, request)`.
+    struct GrpcQuerier<F: Fn(&str, &[u8]) -> ContractResult<Binary>>(F);
+
+    impl<F: Fn(&str, &[u8]) -> ContractResult<Binary>> Querier for GrpcQuerier<F> {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            match from_json(bin_request).unwrap() {
+                QueryRequest::<Empty>::Stargate { path, data } => {
+                    SystemResult::Ok((self.0)(&path, data.as_slice()))
+                }
+                request => panic!("unexpected query {:?}", request),
+            }
+        }
+    }
+
+    /// QueryDenomAuthorityMetadataResponse for the fixture admin, encoded by hand from the
+    /// proto definition: field 1 (authority_metadata) wrapping field 1 (admin).
+    fn authority_metadata_response() -> Vec<u8> {
+        let admin = fixtures::creator().into_string().into_bytes();
+        let mut inner = vec![0x0a, admin.len() as u8];
+        inner.extend(admin);
+        let mut outer = vec![0x0a, inner.len() as u8];
+        outer.extend(inner);
+        outer
+    }
+
+    #[test]
+    fn authority_metadata_response_decodes() {
+        let response =
+            proto::QueryDenomAuthorityMetadataResponse::decode(&authority_metadata_response()[..])
+                .unwrap();
+        assert_eq!(
+            response.authority_metadata.unwrap().admin,
+            fixtures::creator().as_str()
+        );
+    }
+
+    #[test]
+    fn admin_is_queried_from_the_authority_metadata() {
+        let mock = GrpcQuerier(|path, request| {
+            assert_eq!(
+                path,
+                "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata"
+            );
+            let request = proto::QueryDenomAuthorityMetadataRequest::decode(request).unwrap();
+            assert_eq!(request.denom, fixtures::denom());
+            ContractResult::Ok(authority_metadata_response().into())
+        });
+        let querier = StargateTokenFactoryQuerier::new(QuerierWrapper::<Empty>::new(&mock));
+        let response = querier
+            .query_token_factory_admin(fixtures::denom())
+            .unwrap();
+        assert_eq!(response.admin, fixtures::creator().as_str());
+    }
+
+    #[test]
+    fn metadata_error_answer_counts_as_no_metadata() {
+        let mock = GrpcQuerier(|_, _| ContractResult::Err("codespace: sdk, code: 38".to_string()));
+        let querier = StargateTokenFactoryQuerier::new(QuerierWrapper::<Empty>::new(&mock));
+        let response = querier
+            .query_token_factory_metadata(fixtures::denom())
+            .unwrap();
+        assert_eq!(response.metadata, None);
+    }
+
+    #[test]
+    fn admin_error_answer_fails() {
+        let mock = GrpcQuerier(|_, _| ContractResult::Err("codespace: sdk, code: 5".to_string()));
+        let querier = StargateTokenFactoryQuerier::new(QuerierWrapper::<Empty>::new(&mock));
+        querier
+            .query_token_factory_admin(fixtures::denom())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn mint_coin_proto_formats_the_amount() {
+        assert_eq!(
+            mint_coin_proto(fixtures::denom(), Uint256::from(1_000_000u128)).unwrap(),
+            proto::Coin {
+                denom: fixtures::denom(),
+                amount: "1000000".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn mint_coin_proto_rejects_amounts_beyond_uint128() {
+        mint_coin_proto(fixtures::denom(), Uint256::MAX).unwrap_err();
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Int256, StdError, StdResult, Uint256};
+
+use crate::{TokenFactoryError, TokenFactoryMsg};
+
+/// A signed change in the supply of a denom, accumulated from mints and burns.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub struct SupplyDelta(pub Int256);
+
+impl SupplyDelta {
+    pub const fn zero() -> Self {
+        SupplyDelta(Int256::zero())
+    }
+
+    /// Adds a minted amount to the delta.
+    pub fn checked_add_mint(self, amount: Uint256) -> StdResult<Self> {
+        let amount = Int256::try_from(amount)?;
+        Ok(SupplyDelta(self.0.checked_add(amount)?))
+    }
+
+    /// Subtracts a burned amount from the delta.
+    pub fn checked_sub_burn(self, amount: Uint256) -> StdResult<Self> {
+        let amount = Int256::try_from(amount)?;
+        Ok(SupplyDelta(self.0.checked_sub(amount)?))
+    }
+
+    /// Returns true if more was burned than minted.
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+}
+
+impl TryFrom<SupplyDelta> for Uint256 {
+    type Error = StdError;
+
+    /// Converts a non-negative delta into an unsigned amount.
+    fn try_from(delta: SupplyDelta) -> StdResult<Self> {
+        Ok(Uint256::try_from(delta.0)?)
+    }
+}
+
+/// Returns the message that brings the supply held by `me` from `current` to `target`:
+/// a mint to `me` if the target is higher, a burn from `me` if it is lower, or None if
+/// the supply is already at the target.
+pub fn reconcile_supply(
+    denom: String,
+    current: Uint256,
+    target: Uint256,
+    me: &Addr,
+) -> StdResult<Option<TokenFactoryMsg>> {
+    Ok(if target > current {
+        Some(TokenFactoryMsg::MintTokens {
+            denom,
+            amount: target.checked_sub(current)?,
+            mint_to_address: me.clone(),
+        })
+    } else if target < current {
+        Some(TokenFactoryMsg::BurnTokens {
+            denom,
+            amount: current.checked_sub(target)?,
+            burn_from_address: me.clone(),
+        })
+    } else {
+        None
+    })
+}
+
+/// Amounts keyed by denom, for tallying mints, burns or fees across many messages.
+#[cw_serde]
+#[derive(Default)]
+pub struct DenomAmounts(pub BTreeMap<String, Uint256>);
+
+impl DenomAmounts {
+    /// Returns the amount tallied for `denom`, or zero.
+    pub fn get(&self, denom: &str) -> Uint256 {
+        self.0.get(denom).copied().unwrap_or_default()
+    }
+
+    /// Adds `amount` to the tally of `denom`.
+    pub fn add(&mut self, denom: &str, amount: Uint256) -> Result<(), TokenFactoryError> {
+        let total = self.0.entry(denom.to_string()).or_default();
+        *total = total.checked_add(amount)?;
+        Ok(())
+    }
+
+    /// Adds every tally of `other` into this one. On overflow, nothing is merged.
+    pub fn merge(&mut self, other: &DenomAmounts) -> Result<(), TokenFactoryError> {
+        let mut merged = self.clone();
+        for (denom, amount) in &other.0 {
+            merged.add(denom, *amount)?;
+        }
+        *self = merged;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supply_delta_tracks_mints_and_burns() {
+        let delta = SupplyDelta::zero()
+            .checked_add_mint(Uint256::from(100u128))
+            .unwrap()
+            .checked_sub_burn(Uint256::from(30u128))
+            .unwrap()
+            .checked_add_mint(Uint256::from(5u128))
+            .unwrap();
+        assert!(!delta.is_negative());
+        assert_eq!(Uint256::try_from(delta).unwrap(), Uint256::from(75u128));
+    }
+
+    #[test]
+    fn supply_delta_goes_negative_when_burning_more_than_minted() {
+        let delta = SupplyDelta::zero()
+            .checked_add_mint(Uint256::from(10u128))
+            .unwrap()
+            .checked_sub_burn(Uint256::from(25u128))
+            .unwrap();
+        assert!(delta.is_negative());
+        assert_eq!(delta, SupplyDelta(Int256::from(-15i128)));
+        Uint256::try_from(delta).unwrap_err();
+    }
+
+    #[test]
+    fn supply_delta_rejects_amounts_beyond_int256() {
+        SupplyDelta::zero()
+            .checked_add_mint(Uint256::MAX)
+            .unwrap_err();
+    }
+
+    fn amounts(tallies: &[(&str, u128)]) -> DenomAmounts {
+        let mut amounts = DenomAmounts::default();
+        for (denom, amount) in tallies {
+            amounts.add(denom, Uint256::from(*amount)).unwrap();
+        }
+        amounts
+    }
+
+    #[test]
+    fn merge_adds_tallies_per_denom() {
+        let mut total = amounts(&[("uatom", 10), ("uosmo", 5)]);
+        total
+            .merge(&amounts(&[("uosmo", 7), ("uusdx", 3)]))
+            .unwrap();
+        assert_eq!(
+            total,
+            amounts(&[("uatom", 10), ("uosmo", 12), ("uusdx", 3)])
+        );
+    }
+
+    #[test]
+    fn merge_on_overflow_leaves_the_tallies_unchanged() {
+        let mut total = amounts(&[("uatom", 1)]);
+        total.add("uosmo", Uint256::MAX).unwrap();
+        let before = total.clone();
+        let mut other = amounts(&[("uatom", 2)]);
+        other.add("uosmo", Uint256::one()).unwrap();
+        total.merge(&other).unwrap_err();
+        assert_eq!(total, before);
+        assert_eq!(total.get("uatom"), Uint256::one());
+    }
+
+    #[test]
+    fn reconcile_supply_mints_burns_or_does_nothing() {
+        let me = Addr::unchecked("contract");
+        let denom = || "factory/contract/a".to_string();
+        assert_eq!(
+            reconcile_supply(denom(), Uint256::from(10u128), Uint256::from(25u128), &me).unwrap(),
+            Some(TokenFactoryMsg::MintTokens {
+                denom: denom(),
+                amount: Uint256::from(15u128),
+                mint_to_address: me.clone(),
+            })
+        );
+        assert_eq!(
+            reconcile_supply(denom(), Uint256::from(25u128), Uint256::from(10u128), &me).unwrap(),
+            Some(TokenFactoryMsg::BurnTokens {
+                denom: denom(),
+                amount: Uint256::from(15u128),
+                burn_from_address: me.clone(),
+            })
+        );
+        assert_eq!(
+            reconcile_supply(denom(), Uint256::from(10u128), Uint256::from(10u128), &me).unwrap(),
+            None
+        );
+    }
+}
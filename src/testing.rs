@@ -0,0 +1,82 @@
+use cosmwasm_std::testing::{MockQuerier, MockQuerierCustomHandlerResult};
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, ContractResult, CustomQuery, StdResult, SystemError, SystemResult,
+};
+use serde::Serialize;
+
+use crate::{
+    full_denom, AdminResponse, DenomsByCreatorResponse, FullDenomResponse, MetadataResponse,
+    TokenFactoryQuerier, TokenFactoryQuery, TokenParams, TokenParamsResponse,
+};
+
+// Mock querier shared by the tests of the query helpers.
+
+impl CustomQuery for TokenFactoryQuery {}
+
+/// Returns a mock querier holding `balances` that answers token factory queries with
+/// `handler`. Wrap it in a `QuerierWrapper<TokenFactoryQuery>` to call the helpers.
+pub(crate) fn mock_querier(
+    balances: &[(&str, &[Coin])],
+    handler: impl Fn(&TokenFactoryQuery) -> MockQuerierCustomHandlerResult + 'static,
+) -> MockQuerier<TokenFactoryQuery> {
+    MockQuerier::new(balances).with_custom_handler(handler)
+}
+
+/// Answers a query with `response`.
+pub(crate) fn respond(response: &impl Serialize) -> MockQuerierCustomHandlerResult {
+    SystemResult::Ok(ContractResult::Ok(to_json_binary(response).unwrap()))
+}
+
+/// Fails a query the way a chain without support for it does.
+pub(crate) fn unsupported() -> MockQuerierCustomHandlerResult {
+    SystemResult::Err(SystemError::UnsupportedRequest {
+        kind: "token factory".to_string(),
+    })
+}
+
+/// A querier implementing only the required TokenFactoryQuerier methods, to test the
+/// default bodies of the others. Every creator has created `denoms`.
+pub(crate) struct RequiredOnlyQuerier {
+    pub denoms: Vec<String>,
+}
+
+impl TokenFactoryQuerier for RequiredOnlyQuerier {
+    fn query_token_factory_full_denom(
+        &self,
+        subdenom: String,
+        creator_addr: Addr,
+    ) -> StdResult<FullDenomResponse> {
+        Ok(FullDenomResponse {
+            denom: full_denom(&creator_addr, &subdenom),
+        })
+    }
+
+    fn query_token_factory_admin(&self, _denom: String) -> StdResult<AdminResponse> {
+        Ok(AdminResponse {
+            admin: String::new(),
+        })
+    }
+
+    fn query_token_factory_metadata(&self, _denom: String) -> StdResult<MetadataResponse> {
+        Ok(MetadataResponse { metadata: None })
+    }
+
+    fn query_token_factory_denoms_by_creator(
+        &self,
+        _creator: Addr,
+    ) -> StdResult<DenomsByCreatorResponse> {
+        Ok(DenomsByCreatorResponse {
+            denoms: self.denoms.clone(),
+        })
+    }
+
+    fn query_token_factory_params(&self) -> StdResult<TokenParamsResponse> {
+        Ok(TokenParamsResponse {
+            params: TokenParams {
+                denom_creation_fee: vec![],
+                denom_creation_gas_consume: None,
+                max_subdenom_length: None,
+            },
+        })
+    }
+}
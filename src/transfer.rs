@@ -0,0 +1,152 @@
+use bech32::FromBase32;
+use cosmwasm_std::{Addr, Event};
+use sha2::{Digest, Sha256};
+
+use crate::{TokenFactoryError, TokenFactoryMsg};
+
+impl TokenFactoryMsg {
+    /// Returns a ForceTransfer as a `tf_force_transfer` event with `recipient`, `sender` and
+    /// `amount` (e.g. `100factory/osmo1.../foo`) attributes, for indexers tracking compliance
+    /// actions. The chain emits the bank `transfer` event for the move itself; this one only
+    /// marks it as forced, and reaches the chain as `wasm-tf_force_transfer`, like every
+    /// contract event. Returns None for other messages.
+    pub fn as_transfer_event(&self) -> Option<Event> {
+        match self {
+            TokenFactoryMsg::ForceTransfer {
+                denom,
+                from_address,
+                to_address,
+                amount,
+            } => Some(
+                Event::new("tf_force_transfer")
+                    .add_attribute("recipient", to_address)
+                    .add_attribute("sender", from_address)
+                    .add_attribute("amount", format!("{}{}", amount, denom)),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns the ForceTransfer that moves the same amount back, from the recipient to
+    /// the original sender, to undo a compliance action. Returns None for other messages.
+    pub fn reverse_force_transfer(&self) -> Option<TokenFactoryMsg> {
+        match self {
+            TokenFactoryMsg::ForceTransfer {
+                denom,
+                from_address,
+                to_address,
+                amount,
+            } => Some(TokenFactoryMsg::ForceTransfer {
+                denom: denom.clone(),
+                from_address: to_address.clone(),
+                to_address: from_address.clone(),
+                amount: *amount,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Names of the module accounts chains commonly hold tokens in.
+pub const KNOWN_MODULE_ACCOUNTS: &[&str] = &[
+    "fee_collector",
+    "distribution",
+    "bonded_tokens_pool",
+    "not_bonded_tokens_pool",
+    "gov",
+    "mint",
+    "transfer",
+    "tokenfactory",
+];
+
+/// Rejects a ForceTransfer source that is the account of a well-known module.
+///
+/// This is a best-effort heuristic: it derives the address of each module in
+/// [`KNOWN_MODULE_ACCOUNTS`] the way the SDK does (first 20 bytes of the SHA-256 of the
+/// module name) and compares it with the bech32 data of `from`. Modules outside that
+/// list, and addresses that are not bech32, are not detected.
+pub fn validate_force_transfer_source_not_module(from: &Addr) -> Result<(), TokenFactoryError> {
+    let data = match bech32::decode(from.as_str()) {
+        Ok((_, data, _)) => Vec::<u8>::from_base32(&data).unwrap_or_default(),
+        Err(_) => return Ok(()),
+    };
+    for module in KNOWN_MODULE_ACCOUNTS {
+        if Sha256::digest(module.as_bytes())[..20] == data[..] {
+            return Err(TokenFactoryError::ModuleAccountSource {
+                address: from.to_string(),
+                module: module.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bech32::{ToBase32, Variant};
+    use cosmwasm_std::Uint256;
+
+    use super::*;
+    use crate::fixtures;
+
+    fn module_address(module: &str) -> Addr {
+        let data = &Sha256::digest(module.as_bytes())[..20];
+        Addr::unchecked(bech32::encode("osmo", data.to_base32(), Variant::Bech32).unwrap())
+    }
+
+    #[test]
+    fn module_account_source_is_rejected() {
+        let err = validate_force_transfer_source_not_module(&module_address("fee_collector"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            TokenFactoryError::ModuleAccountSource { module, .. } if module == "fee_collector"
+        ));
+    }
+
+    #[test]
+    fn user_account_source_passes() {
+        let user = bech32::encode("osmo", [7u8; 20].to_base32(), Variant::Bech32).unwrap();
+        validate_force_transfer_source_not_module(&Addr::unchecked(user)).unwrap();
+    }
+
+    #[test]
+    fn non_bech32_source_passes() {
+        validate_force_transfer_source_not_module(&Addr::unchecked("fee_collector")).unwrap();
+    }
+
+    #[test]
+    fn force_transfer_event_carries_the_transfer() {
+        let event = fixtures::force_transfer().as_transfer_event().unwrap();
+        assert_eq!(
+            event,
+            Event::new("tf_force_transfer")
+                .add_attribute("recipient", fixtures::creator())
+                .add_attribute("sender", fixtures::user())
+                .add_attribute("amount", format!("250000{}", fixtures::denom()))
+        );
+    }
+
+    #[test]
+    fn other_messages_have_no_transfer_event() {
+        assert_eq!(fixtures::mint_tokens().as_transfer_event(), None);
+    }
+
+    #[test]
+    fn reverse_force_transfer_swaps_the_addresses() {
+        assert_eq!(
+            fixtures::force_transfer().reverse_force_transfer(),
+            Some(TokenFactoryMsg::ForceTransfer {
+                denom: fixtures::denom(),
+                from_address: fixtures::creator(),
+                to_address: fixtures::user(),
+                amount: Uint256::from(250_000u128),
+            })
+        );
+    }
+
+    #[test]
+    fn other_messages_have_no_reverse_force_transfer() {
+        assert_eq!(fixtures::mint_tokens().reverse_force_transfer(), None);
+    }
+}
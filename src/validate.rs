@@ -0,0 +1,145 @@
+use crate::denom::is_valid_denom;
+use crate::{Subdenom, TokenFactoryError, TokenFactoryMsg};
+
+impl TokenFactoryMsg {
+    /// Checks the message against the rules the chain enforces before submitting it:
+    /// subdenom rules and metadata for CreateDenom, a valid denom for every other message,
+    /// a nonzero amount for mints, burns and force transfers, valid metadata for SetMetadata
+    /// and distinct addresses for ForceTransfer.
+    pub fn validate(&self) -> Result<(), TokenFactoryError> {
+        match self {
+            TokenFactoryMsg::CreateDenom { subdenom, metadata } => {
+                Subdenom::new(subdenom.as_str())?;
+                if let Some(metadata) = metadata {
+                    metadata.validate()?;
+                }
+            }
+            TokenFactoryMsg::ChangeAdmin { denom, .. }
+            | TokenFactoryMsg::SetBeforeSendHook { denom, .. } => validate_denom(denom)?,
+            #[cfg(feature = "multi-admin")]
+            TokenFactoryMsg::ChangeAdmins { denom, .. } => validate_denom(denom)?,
+            TokenFactoryMsg::MintTokens { denom, amount, .. }
+            | TokenFactoryMsg::BurnTokens { denom, amount, .. } => {
+                validate_denom(denom)?;
+                if amount.is_zero() {
+                    return Err(TokenFactoryError::ZeroAmount {});
+                }
+            }
+            TokenFactoryMsg::SetMetadata { metadata } => metadata.validate()?,
+            TokenFactoryMsg::ForceTransfer {
+                denom,
+                from_address,
+                to_address,
+                amount,
+            } => {
+                validate_denom(denom)?;
+                if amount.is_zero() {
+                    return Err(TokenFactoryError::ZeroAmount {});
+                }
+                if from_address == to_address {
+                    return Err(TokenFactoryError::ForceTransferToSelf {
+                        address: from_address.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_denom(denom: &str) -> Result<(), TokenFactoryError> {
+    if !is_valid_denom(denom) {
+        return Err(TokenFactoryError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Uint256;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn create_denom_rejects_an_invalid_subdenom() {
+        let msg = TokenFactoryMsg::CreateDenom {
+            subdenom: "u usdx".to_string(),
+            metadata: None,
+        };
+        assert!(matches!(
+            msg.validate().unwrap_err(),
+            TokenFactoryError::InvalidSubdenom { .. }
+        ));
+    }
+
+    #[test]
+    fn messages_reject_an_invalid_denom() {
+        let msg = TokenFactoryMsg::ChangeAdmin {
+            denom: "1nvalid".to_string(),
+            new_admin_address: fixtures::new_admin(),
+        };
+        assert_eq!(
+            msg.validate().unwrap_err(),
+            TokenFactoryError::InvalidDenom {
+                denom: "1nvalid".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn mints_burns_and_force_transfers_reject_a_zero_amount() {
+        let zero = Uint256::zero();
+        let msgs = [
+            TokenFactoryMsg::MintTokens {
+                denom: fixtures::denom(),
+                amount: zero,
+                mint_to_address: fixtures::user(),
+            },
+            TokenFactoryMsg::BurnTokens {
+                denom: fixtures::denom(),
+                amount: zero,
+                burn_from_address: fixtures::creator(),
+            },
+            TokenFactoryMsg::ForceTransfer {
+                denom: fixtures::denom(),
+                amount: zero,
+                from_address: fixtures::user(),
+                to_address: fixtures::creator(),
+            },
+        ];
+        for msg in msgs {
+            assert_eq!(
+                msg.validate().unwrap_err(),
+                TokenFactoryError::ZeroAmount {}
+            );
+        }
+    }
+
+    #[test]
+    fn set_metadata_rejects_invalid_metadata() {
+        let mut metadata = fixtures::metadata();
+        metadata.display = "unlisted".to_string();
+        TokenFactoryMsg::SetMetadata { metadata }
+            .validate()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn force_transfer_rejects_a_transfer_to_self() {
+        let msg = TokenFactoryMsg::ForceTransfer {
+            denom: fixtures::denom(),
+            amount: Uint256::from(1u128),
+            from_address: fixtures::user(),
+            to_address: fixtures::user(),
+        };
+        assert_eq!(
+            msg.validate().unwrap_err(),
+            TokenFactoryError::ForceTransferToSelf {
+                address: fixtures::user().into_string(),
+            }
+        );
+    }
+}